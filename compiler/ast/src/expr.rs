@@ -0,0 +1,398 @@
+use psh_syntax::{SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken};
+
+use crate::{ast_node, child, children, token, tokens, AstNode, ParamList};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Expr {
+    IntLiteral(IntLiteral),
+    FractionLiteral(FractionLiteral),
+    BoolLiteral(BoolLiteral),
+    StringLiteral(StringLiteral),
+    StringInterp(StringInterp),
+    VariableRef(VariableRef),
+    UnaryExpr(UnaryExpr),
+    InfixExpr(InfixExpr),
+    PipeExpr(PipeExpr),
+    IfThenElseExpr(IfThenElseExpr),
+    Unit(Unit),
+    ParenExpr(ParenExpr),
+    TupleExpr(TupleExpr),
+    ArrayExpr(ArrayExpr),
+    MapExpr(MapExpr),
+    IndexExpr(IndexExpr),
+    FieldExpr(FieldExpr),
+    CallExpr(CallExpr),
+    LambdaExpr(LambdaExpr),
+    CommandExpr(CommandExpr),
+}
+
+impl AstNode for Expr {
+    fn cast(node: SyntaxNode) -> Option<Self> {
+        let expr = match node.kind() {
+            SyntaxKind::IntLiteral => Self::IntLiteral(IntLiteral(node)),
+            SyntaxKind::FractionLiteral => Self::FractionLiteral(FractionLiteral(node)),
+            SyntaxKind::BoolLiteral => Self::BoolLiteral(BoolLiteral(node)),
+            SyntaxKind::StringLiteral => Self::StringLiteral(StringLiteral(node)),
+            SyntaxKind::StringInterp => Self::StringInterp(StringInterp(node)),
+            SyntaxKind::VariableRef => Self::VariableRef(VariableRef(node)),
+            SyntaxKind::UnaryExpr => Self::UnaryExpr(UnaryExpr(node)),
+            SyntaxKind::InfixExpr => Self::InfixExpr(InfixExpr(node)),
+            SyntaxKind::PipeExpr => Self::PipeExpr(PipeExpr(node)),
+            SyntaxKind::IfThenElseExpr => Self::IfThenElseExpr(IfThenElseExpr(node)),
+            SyntaxKind::Unit => Self::Unit(Unit(node)),
+            SyntaxKind::ParenExpr => Self::ParenExpr(ParenExpr(node)),
+            SyntaxKind::TupleExpr => Self::TupleExpr(TupleExpr(node)),
+            SyntaxKind::ArrayExpr => Self::ArrayExpr(ArrayExpr(node)),
+            SyntaxKind::MapExpr => Self::MapExpr(MapExpr(node)),
+            SyntaxKind::IndexExpr => Self::IndexExpr(IndexExpr(node)),
+            SyntaxKind::FieldExpr => Self::FieldExpr(FieldExpr(node)),
+            SyntaxKind::CallExpr => Self::CallExpr(CallExpr(node)),
+            SyntaxKind::LambdaExpr => Self::LambdaExpr(LambdaExpr(node)),
+            SyntaxKind::CommandExpr => Self::CommandExpr(CommandExpr(node)),
+            _ => return None,
+        };
+
+        Some(expr)
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        match self {
+            Self::IntLiteral(expr) => expr.syntax(),
+            Self::FractionLiteral(expr) => expr.syntax(),
+            Self::BoolLiteral(expr) => expr.syntax(),
+            Self::StringLiteral(expr) => expr.syntax(),
+            Self::StringInterp(expr) => expr.syntax(),
+            Self::VariableRef(expr) => expr.syntax(),
+            Self::UnaryExpr(expr) => expr.syntax(),
+            Self::InfixExpr(expr) => expr.syntax(),
+            Self::PipeExpr(expr) => expr.syntax(),
+            Self::IfThenElseExpr(expr) => expr.syntax(),
+            Self::Unit(expr) => expr.syntax(),
+            Self::ParenExpr(expr) => expr.syntax(),
+            Self::TupleExpr(expr) => expr.syntax(),
+            Self::ArrayExpr(expr) => expr.syntax(),
+            Self::MapExpr(expr) => expr.syntax(),
+            Self::IndexExpr(expr) => expr.syntax(),
+            Self::FieldExpr(expr) => expr.syntax(),
+            Self::CallExpr(expr) => expr.syntax(),
+            Self::LambdaExpr(expr) => expr.syntax(),
+            Self::CommandExpr(expr) => expr.syntax(),
+        }
+    }
+}
+
+ast_node!(IntLiteral);
+
+impl IntLiteral {
+    pub fn token(&self) -> Option<SyntaxToken> {
+        token(&self.0, SyntaxKind::Integer)
+    }
+}
+
+ast_node!(FractionLiteral);
+
+impl FractionLiteral {
+    pub fn token(&self) -> Option<SyntaxToken> {
+        token(&self.0, SyntaxKind::Fraction)
+    }
+}
+
+ast_node!(BoolLiteral);
+
+impl BoolLiteral {
+    /// Whether the literal is `true`, or `None` if it is neither `true` nor `false`.
+    pub fn value(&self) -> Option<bool> {
+        match self.0.first_token()?.kind() {
+            SyntaxKind::TrueKw => Some(true),
+            SyntaxKind::FalseKw => Some(false),
+            _ => None,
+        }
+    }
+}
+
+ast_node!(StringLiteral);
+
+impl StringLiteral {
+    /// The string token, which is a plain, raw or multi-line string.
+    pub fn token(&self) -> Option<SyntaxToken> {
+        self.0
+            .children_with_tokens()
+            .filter_map(SyntaxElement::into_token)
+            .find(|token| {
+                matches!(
+                    token.kind(),
+                    SyntaxKind::String | SyntaxKind::RawString | SyntaxKind::MultilineString
+                )
+            })
+    }
+}
+
+ast_node!(StringInterp);
+
+impl StringInterp {
+    /// The text and interpolated expressions of the string, in order.
+    ///
+    /// The first and last pieces of text still have the string's quotes on them.
+    pub fn parts(&self) -> impl Iterator<Item = StringPart> {
+        self.0
+            .children_with_tokens()
+            .filter_map(|element| match element {
+                SyntaxElement::Token(token) => matches!(
+                    token.kind(),
+                    SyntaxKind::StringStart | SyntaxKind::StringFragment | SyntaxKind::StringEnd
+                )
+                .then_some(StringPart::Text(token)),
+                SyntaxElement::Node(node) => Expr::cast(node).map(StringPart::Expr),
+            })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum StringPart {
+    Text(SyntaxToken),
+    Expr(Expr),
+}
+
+ast_node!(VariableRef);
+
+impl VariableRef {
+    pub fn path(&self) -> Option<Path> {
+        child(&self.0)
+    }
+}
+
+ast_node!(Path);
+
+impl Path {
+    pub fn segments(&self) -> impl Iterator<Item = SyntaxToken> {
+        tokens(&self.0, SyntaxKind::Ident)
+    }
+}
+
+ast_node!(UnaryExpr);
+
+impl UnaryExpr {
+    pub fn op(&self) -> Option<SyntaxToken> {
+        token(&self.0, SyntaxKind::Minus).or_else(|| token(&self.0, SyntaxKind::NotKw))
+    }
+
+    pub fn expr(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+ast_node!(InfixExpr);
+
+impl InfixExpr {
+    pub fn lhs(&self) -> Option<Expr> {
+        children(&self.0).next()
+    }
+
+    pub fn rhs(&self) -> Option<Expr> {
+        children(&self.0).nth(1)
+    }
+
+    pub fn op(&self) -> Option<SyntaxToken> {
+        self.0
+            .children_with_tokens()
+            .filter_map(|element| element.into_token())
+            .find(|token| {
+                matches!(
+                    token.kind(),
+                    SyntaxKind::Plus
+                        | SyntaxKind::Minus
+                        | SyntaxKind::Star
+                        | SyntaxKind::Slash
+                        | SyntaxKind::DoubleEquals
+                        | SyntaxKind::BangEquals
+                        | SyntaxKind::LAngle
+                        | SyntaxKind::LAngleEquals
+                        | SyntaxKind::RAngle
+                        | SyntaxKind::RAngleEquals
+                        | SyntaxKind::AndKw
+                        | SyntaxKind::OrKw,
+                )
+            })
+    }
+}
+
+ast_node!(PipeExpr);
+
+impl PipeExpr {
+    pub fn lhs(&self) -> Option<Expr> {
+        children(&self.0).next()
+    }
+
+    pub fn rhs(&self) -> Option<Expr> {
+        children(&self.0).nth(1)
+    }
+}
+
+ast_node!(IfThenElseExpr);
+
+impl IfThenElseExpr {
+    pub fn condition(&self) -> Option<Expr> {
+        child(child::<IfExpr>(&self.0)?.syntax())
+    }
+
+    pub fn then_branch(&self) -> Option<Expr> {
+        child(child::<ThenExpr>(&self.0)?.syntax())
+    }
+
+    pub fn else_branch(&self) -> Option<Expr> {
+        child(child::<ElseExpr>(&self.0)?.syntax())
+    }
+}
+
+ast_node!(IfExpr);
+ast_node!(ThenExpr);
+ast_node!(ElseExpr);
+
+ast_node!(Unit);
+
+ast_node!(ParenExpr);
+
+impl ParenExpr {
+    pub fn expr(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+ast_node!(TupleExpr);
+
+impl TupleExpr {
+    pub fn elements(&self) -> impl Iterator<Item = Expr> {
+        children(&self.0)
+    }
+}
+
+ast_node!(ArrayExpr);
+
+impl ArrayExpr {
+    pub fn elements(&self) -> impl Iterator<Item = Expr> {
+        children(&self.0)
+    }
+}
+
+ast_node!(MapExpr);
+
+impl MapExpr {
+    pub fn entries(&self) -> impl Iterator<Item = MapEntry> {
+        children(&self.0)
+    }
+}
+
+ast_node!(MapEntry);
+
+impl MapEntry {
+    /// The key of the entry, either an identifier or a string token.
+    pub fn key(&self) -> Option<SyntaxToken> {
+        self.0
+            .children_with_tokens()
+            .filter_map(|element| element.into_token())
+            .find(|token| matches!(token.kind(), SyntaxKind::Ident | SyntaxKind::String))
+    }
+
+    pub fn value(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+ast_node!(IndexExpr);
+
+impl IndexExpr {
+    pub fn base(&self) -> Option<Expr> {
+        children(&self.0).next()
+    }
+
+    pub fn index(&self) -> Option<Expr> {
+        children(&self.0).nth(1)
+    }
+}
+
+ast_node!(FieldExpr);
+
+impl FieldExpr {
+    pub fn base(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+
+    pub fn field(&self) -> Option<SyntaxToken> {
+        token(&self.0, SyntaxKind::Ident)
+    }
+}
+
+ast_node!(CallExpr);
+
+impl CallExpr {
+    pub fn callee(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+
+    pub fn arg_list(&self) -> Option<ArgList> {
+        child(&self.0)
+    }
+}
+
+ast_node!(ArgList);
+
+impl ArgList {
+    pub fn args(&self) -> impl Iterator<Item = Expr> {
+        children(&self.0)
+    }
+}
+
+ast_node!(LambdaExpr);
+
+impl LambdaExpr {
+    pub fn param_list(&self) -> Option<ParamList> {
+        child(&self.0)
+    }
+
+    pub fn body(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+ast_node!(CommandExpr);
+
+impl CommandExpr {
+    /// The commands of the pipeline, in order.
+    pub fn commands(&self) -> impl Iterator<Item = Command> {
+        children(&self.0)
+    }
+}
+
+ast_node!(Command);
+
+impl Command {
+    /// The command name followed by its arguments, each either a word or a quoted string.
+    pub fn words(&self) -> impl Iterator<Item = SyntaxToken> {
+        self.0
+            .children_with_tokens()
+            .filter_map(|element| element.into_token())
+            .filter(|token| matches!(token.kind(), SyntaxKind::CommandWord | SyntaxKind::String))
+    }
+
+    /// The redirections of the command, in the order they were written.
+    pub fn redirects(&self) -> impl Iterator<Item = Redirect> {
+        children(&self.0)
+    }
+}
+
+ast_node!(Redirect);
+
+impl Redirect {
+    /// The redirection operator, like `>` or `2>&1`.
+    pub fn op(&self) -> Option<SyntaxToken> {
+        self.0.first_token()
+    }
+
+    /// The file being redirected to or from, either a word or a quoted string.
+    pub fn target(&self) -> Option<SyntaxToken> {
+        self.0
+            .children_with_tokens()
+            .filter_map(|element| element.into_token())
+            .find(|token| matches!(token.kind(), SyntaxKind::CommandWord | SyntaxKind::String))
+    }
+}
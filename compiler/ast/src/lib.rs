@@ -0,0 +1,66 @@
+use psh_syntax::{SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken};
+
+pub use crate::expr::*;
+pub use crate::pattern::*;
+pub use crate::stmt::*;
+pub use crate::types::*;
+
+mod expr;
+mod pattern;
+mod stmt;
+mod types;
+
+#[cfg(test)]
+mod tests;
+
+/// A typed view over a node in the syntax tree.
+///
+/// Every implementor is a newtype around a [`SyntaxNode`], so casting is only a check of the
+/// node’s kind.
+pub trait AstNode: Sized {
+    fn cast(node: SyntaxNode) -> Option<Self>;
+
+    fn syntax(&self) -> &SyntaxNode;
+}
+
+macro_rules! ast_node {
+    ($name:ident) => {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        #[repr(transparent)]
+        pub struct $name(psh_syntax::SyntaxNode);
+
+        impl $crate::AstNode for $name {
+            fn cast(node: psh_syntax::SyntaxNode) -> Option<Self> {
+                (node.kind() == psh_syntax::SyntaxKind::$name).then(|| Self(node))
+            }
+
+            fn syntax(&self) -> &psh_syntax::SyntaxNode {
+                &self.0
+            }
+        }
+    };
+}
+
+pub(crate) use ast_node;
+
+fn child<N: AstNode>(parent: &SyntaxNode) -> Option<N> {
+    parent.children().find_map(N::cast)
+}
+
+fn children<N: AstNode>(parent: &SyntaxNode) -> impl Iterator<Item = N> {
+    parent.children().filter_map(N::cast)
+}
+
+fn token(parent: &SyntaxNode, kind: SyntaxKind) -> Option<SyntaxToken> {
+    parent
+        .children_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .find(|token| token.kind() == kind)
+}
+
+fn tokens(parent: &SyntaxNode, kind: SyntaxKind) -> impl Iterator<Item = SyntaxToken> {
+    parent
+        .children_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .filter(move |token| token.kind() == kind)
+}
@@ -0,0 +1,101 @@
+use psh_syntax::{SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken};
+
+use crate::{ast_node, child, children, AstNode, TypeAnnotation};
+
+/// A pattern that binds names, e.g. in a value definition or a parameter.
+///
+/// Binding a single name is the most common pattern, so it is a bare identifier token rather
+/// than a node of its own.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Pattern {
+    Name(SyntaxToken),
+    Unit(UnitPattern),
+    Paren(ParenPattern),
+    Tuple(TuplePattern),
+}
+
+impl Pattern {
+    #[must_use]
+    pub fn cast(element: SyntaxElement) -> Option<Self> {
+        let pattern = match element {
+            SyntaxElement::Token(token) if token.kind() == SyntaxKind::Ident => Self::Name(token),
+            SyntaxElement::Token(_) => return None,
+            SyntaxElement::Node(node) => match node.kind() {
+                SyntaxKind::UnitPattern => Self::Unit(UnitPattern(node)),
+                SyntaxKind::ParenPattern => Self::Paren(ParenPattern(node)),
+                SyntaxKind::TuplePattern => Self::Tuple(TuplePattern(node)),
+                _ => return None,
+            },
+        };
+
+        Some(pattern)
+    }
+
+    #[must_use]
+    pub fn syntax(&self) -> SyntaxElement {
+        match self {
+            Self::Name(name) => name.clone().into(),
+            Self::Unit(pattern) => pattern.syntax().clone().into(),
+            Self::Paren(pattern) => pattern.syntax().clone().into(),
+            Self::Tuple(pattern) => pattern.syntax().clone().into(),
+        }
+    }
+}
+
+fn pattern(parent: &SyntaxNode) -> Option<Pattern> {
+    parent.children_with_tokens().find_map(Pattern::cast)
+}
+
+ast_node!(UnitPattern);
+
+ast_node!(ParenPattern);
+
+impl ParenPattern {
+    pub fn pattern(&self) -> Option<Pattern> {
+        pattern(&self.0)
+    }
+
+    pub fn type_annotation(&self) -> Option<TypeAnnotation> {
+        child(&self.0)
+    }
+}
+
+ast_node!(TuplePattern);
+
+impl TuplePattern {
+    pub fn args(&self) -> impl Iterator<Item = TuplePatternArg> {
+        children(&self.0)
+    }
+}
+
+ast_node!(TuplePatternArg);
+
+impl TuplePatternArg {
+    pub fn pattern(&self) -> Option<Pattern> {
+        pattern(&self.0)
+    }
+
+    pub fn type_annotation(&self) -> Option<TypeAnnotation> {
+        child(&self.0)
+    }
+}
+
+ast_node!(ParamList);
+
+impl ParamList {
+    pub fn params(&self) -> impl Iterator<Item = Param> {
+        children(&self.0)
+    }
+}
+
+ast_node!(Param);
+
+impl Param {
+    pub fn pattern(&self) -> Option<Pattern> {
+        pattern(&self.0)
+    }
+
+    pub fn type_annotation(&self) -> Option<TypeAnnotation> {
+        child(&self.0)
+    }
+}
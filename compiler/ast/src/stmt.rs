@@ -0,0 +1,78 @@
+use psh_syntax::{SyntaxKind, SyntaxNode, SyntaxToken};
+
+use crate::{ast_node, child, children, token, AstNode, Expr, ParamList, Pattern, TypeAnnotation};
+
+ast_node!(SourceFile);
+
+impl SourceFile {
+    pub fn stmts(&self) -> impl Iterator<Item = Stmt> {
+        children(&self.0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Stmt {
+    ValueDef(ValueDef),
+    TypeSignature(TypeSignature),
+    Expr(Expr),
+}
+
+impl AstNode for Stmt {
+    fn cast(node: SyntaxNode) -> Option<Self> {
+        let stmt = match node.kind() {
+            SyntaxKind::ValueDef => Self::ValueDef(ValueDef(node)),
+            SyntaxKind::TypeSignature => Self::TypeSignature(TypeSignature(node)),
+            _ => Self::Expr(Expr::cast(node)?),
+        };
+
+        Some(stmt)
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        match self {
+            Self::ValueDef(value_def) => value_def.syntax(),
+            Self::TypeSignature(type_signature) => type_signature.syntax(),
+            Self::Expr(expr) => expr.syntax(),
+        }
+    }
+}
+
+ast_node!(ValueDef);
+
+impl ValueDef {
+    pub fn pattern(&self) -> Option<Pattern> {
+        self.0.children_with_tokens().find_map(Pattern::cast)
+    }
+
+    /// The name being defined, if the value is bound to a name rather than destructured.
+    pub fn name(&self) -> Option<SyntaxToken> {
+        match self.pattern()? {
+            Pattern::Name(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    pub fn param_list(&self) -> Option<ParamList> {
+        child(&self.0)
+    }
+
+    pub fn type_annotation(&self) -> Option<TypeAnnotation> {
+        child(&self.0)
+    }
+
+    pub fn value(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+ast_node!(TypeSignature);
+
+impl TypeSignature {
+    pub fn name(&self) -> Option<SyntaxToken> {
+        token(&self.0, SyntaxKind::Ident)
+    }
+
+    pub fn type_annotation(&self) -> Option<TypeAnnotation> {
+        child(&self.0)
+    }
+}
@@ -0,0 +1,339 @@
+use std::fs;
+use std::path::Path;
+
+use psh_syntax::SyntaxKind;
+
+use crate::*;
+
+fn parse(input: &str) -> SourceFile {
+    let parse = psh_parser::parse_repl_line(input);
+
+    SourceFile::cast(parse.syntax()).unwrap()
+}
+
+fn parse_expr(input: &str) -> Expr {
+    match parse(input).stmts().next() {
+        Some(Stmt::Expr(expr)) => expr,
+        stmt => panic!("expected an expression, found {stmt:?}"),
+    }
+}
+
+fn parse_value_def(input: &str) -> ValueDef {
+    match parse(input).stmts().next() {
+        Some(Stmt::ValueDef(value_def)) => value_def,
+        stmt => panic!("expected a value definition, found {stmt:?}"),
+    }
+}
+
+fn fixture_inputs() -> Vec<(String, String)> {
+    let tests_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../parser/src/tests/repl_line");
+
+    let mut inputs: Vec<_> = fs::read_dir(tests_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "test"))
+        .map(|path| {
+            let test_content = fs::read_to_string(&path).unwrap();
+            let (input, _expected) = test_content.split_once("\n===\n").unwrap();
+            let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+
+            (name, input.to_string())
+        })
+        .collect();
+    inputs.sort();
+
+    assert!(!inputs.is_empty(), "no parser fixtures found");
+    inputs
+}
+
+/// Whether the accessors of `expr` find every part that an expression without errors has.
+fn has_children(expr: &Expr) -> bool {
+    match expr {
+        Expr::IntLiteral(literal) => literal.token().is_some(),
+        Expr::FractionLiteral(literal) => literal.token().is_some(),
+        Expr::BoolLiteral(literal) => literal.value().is_some(),
+        Expr::StringLiteral(literal) => literal.token().is_some(),
+        Expr::StringInterp(interp) => interp.parts().next().is_some(),
+        Expr::VariableRef(variable_ref) => variable_ref
+            .path()
+            .is_some_and(|path| path.segments().next().is_some()),
+        Expr::UnaryExpr(unary) => unary.op().is_some() && unary.expr().is_some(),
+        Expr::InfixExpr(infix) => {
+            infix.op().is_some() && infix.lhs().is_some() && infix.rhs().is_some()
+        }
+        Expr::PipeExpr(pipe) => pipe.lhs().is_some() && pipe.rhs().is_some(),
+        Expr::IfThenElseExpr(if_then_else) => {
+            if_then_else.condition().is_some()
+                && if_then_else.then_branch().is_some()
+                && if_then_else.else_branch().is_some()
+        }
+        Expr::Unit(_) | Expr::TupleExpr(_) | Expr::ArrayExpr(_) => true,
+        Expr::ParenExpr(paren) => paren.expr().is_some(),
+        Expr::MapExpr(map) => map
+            .entries()
+            .all(|entry| entry.key().is_some() && entry.value().is_some()),
+        Expr::IndexExpr(index) => index.base().is_some() && index.index().is_some(),
+        Expr::FieldExpr(field) => field.base().is_some() && field.field().is_some(),
+        Expr::CallExpr(call) => call.callee().is_some() && call.arg_list().is_some(),
+        Expr::LambdaExpr(lambda) => lambda.param_list().is_some() && lambda.body().is_some(),
+        Expr::CommandExpr(command) => command.commands().next().is_some(),
+    }
+}
+
+#[test]
+fn every_fixture_casts_to_typed_nodes() {
+    for (name, input) in fixture_inputs() {
+        let parse = psh_parser::parse_repl_line(&input);
+        let source_file = SourceFile::cast(parse.syntax())
+            .unwrap_or_else(|| panic!("{name}: root is not a source file"));
+
+        for node in source_file.syntax().descendants() {
+            let kind = node.kind();
+            let casts = match kind {
+                SyntaxKind::SourceFile => SourceFile::cast(node.clone()).is_some(),
+                SyntaxKind::ValueDef | SyntaxKind::TypeSignature => {
+                    Stmt::cast(node.clone()).is_some()
+                }
+                SyntaxKind::NamedType
+                | SyntaxKind::UnitType
+                | SyntaxKind::ParenType
+                | SyntaxKind::TupleType
+                | SyntaxKind::ArrayType
+                | SyntaxKind::MapType
+                | SyntaxKind::FunctionType => Type::cast(node.clone()).is_some(),
+                SyntaxKind::UnitPattern | SyntaxKind::ParenPattern | SyntaxKind::TuplePattern => {
+                    Pattern::cast(node.clone().into()).is_some()
+                }
+                SyntaxKind::IntLiteral
+                | SyntaxKind::FractionLiteral
+                | SyntaxKind::BoolLiteral
+                | SyntaxKind::StringLiteral
+                | SyntaxKind::StringInterp
+                | SyntaxKind::VariableRef
+                | SyntaxKind::UnaryExpr
+                | SyntaxKind::InfixExpr
+                | SyntaxKind::PipeExpr
+                | SyntaxKind::IfThenElseExpr
+                | SyntaxKind::Unit
+                | SyntaxKind::ParenExpr
+                | SyntaxKind::TupleExpr
+                | SyntaxKind::ArrayExpr
+                | SyntaxKind::MapExpr
+                | SyntaxKind::IndexExpr
+                | SyntaxKind::FieldExpr
+                | SyntaxKind::CallExpr
+                | SyntaxKind::LambdaExpr
+                | SyntaxKind::CommandExpr => {
+                    Expr::cast(node.clone()).is_some_and(|expr| expr.syntax() == &node)
+                }
+                _ => Expr::cast(node.clone()).is_none(),
+            };
+
+            assert!(casts, "{name}: {kind:?} did not cast to its typed node");
+        }
+
+        if parse.errors().is_empty() {
+            for expr in source_file.syntax().descendants().filter_map(Expr::cast) {
+                assert!(
+                    has_children(&expr),
+                    "{name}: {expr:?} is missing a child it was parsed with"
+                );
+            }
+        }
+
+        for stmt in source_file.stmts() {
+            assert_eq!(stmt.syntax().parent().as_ref(), Some(source_file.syntax()));
+        }
+
+        if parse.errors().is_empty() {
+            for stmt in source_file.stmts() {
+                if let Stmt::ValueDef(value_def) = stmt {
+                    assert!(value_def.pattern().is_some(), "{name}: missing pattern");
+                    assert!(value_def.value().is_some(), "{name}: missing value");
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn value_def_name_and_value() {
+    let value_def = parse_value_def("let a = 1");
+
+    assert_eq!(value_def.name().unwrap().text(), "a");
+    assert!(value_def.param_list().is_none());
+    assert!(matches!(value_def.value(), Some(Expr::IntLiteral(_))));
+}
+
+#[test]
+fn value_def_with_destructured_pattern_has_no_name() {
+    let value_def = parse_value_def("let (a, b) = (1, 2)");
+
+    assert!(value_def.name().is_none());
+    let Some(Pattern::Tuple(tuple)) = value_def.pattern() else {
+        panic!("expected a tuple pattern");
+    };
+    let names: Vec<_> = tuple
+        .args()
+        .filter_map(|arg| match arg.pattern() {
+            Some(Pattern::Name(name)) => Some(name.text().to_string()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(names, ["a", "b"]);
+}
+
+#[test]
+fn value_def_params_and_annotation() {
+    let value_def = parse_value_def("let add (a: Number) b : Number = a + b");
+
+    let params: Vec<_> = value_def.param_list().unwrap().params().collect();
+    assert_eq!(params.len(), 2);
+    assert!(value_def.type_annotation().unwrap().ty().is_some());
+}
+
+#[test]
+fn infix_expr_op_and_operands() {
+    let Expr::InfixExpr(infix) = parse_expr("1 + 2 * 3") else {
+        panic!("expected an infix expression");
+    };
+
+    assert_eq!(infix.op().unwrap().kind(), SyntaxKind::Plus);
+    assert!(matches!(infix.lhs(), Some(Expr::IntLiteral(_))));
+    let Some(Expr::InfixExpr(rhs)) = infix.rhs() else {
+        panic!("expected a nested infix expression");
+    };
+    assert_eq!(rhs.op().unwrap().kind(), SyntaxKind::Star);
+}
+
+#[test]
+fn if_then_else_branches() {
+    let Expr::IfThenElseExpr(if_then_else) = parse_expr("if a then 1 else 2") else {
+        panic!("expected an if-then-else expression");
+    };
+
+    assert!(matches!(
+        if_then_else.condition(),
+        Some(Expr::VariableRef(_))
+    ));
+    assert!(matches!(
+        if_then_else.then_branch(),
+        Some(Expr::IntLiteral(_))
+    ));
+    assert!(matches!(
+        if_then_else.else_branch(),
+        Some(Expr::IntLiteral(_))
+    ));
+}
+
+#[test]
+fn tuple_expr_elements() {
+    let Expr::TupleExpr(tuple) = parse_expr("(1, \"a\", b)") else {
+        panic!("expected a tuple expression");
+    };
+
+    let elements: Vec<_> = tuple.elements().collect();
+    assert!(matches!(
+        elements.as_slice(),
+        [
+            Expr::IntLiteral(_),
+            Expr::StringLiteral(_),
+            Expr::VariableRef(_)
+        ]
+    ));
+}
+
+#[test]
+fn variable_ref_path_segments() {
+    let Expr::VariableRef(variable_ref) = parse_expr("a::b::c") else {
+        panic!("expected a variable reference");
+    };
+
+    let segments: Vec<_> = variable_ref
+        .path()
+        .unwrap()
+        .segments()
+        .map(|segment| segment.text().to_string())
+        .collect();
+    assert_eq!(segments, ["a", "b", "c"]);
+}
+
+#[test]
+fn missing_operand_is_none() {
+    let Expr::InfixExpr(infix) = parse_expr("1 +") else {
+        panic!("expected an infix expression");
+    };
+
+    assert!(infix.lhs().is_some());
+    assert!(infix.rhs().is_none());
+}
+
+#[test]
+fn command_expr_pipeline() {
+    let Expr::CommandExpr(command_expr) = parse_expr("$ ls -la | grep \"a b\"") else {
+        panic!("expected a command expression");
+    };
+
+    let commands: Vec<Vec<_>> = command_expr
+        .commands()
+        .map(|command| {
+            command
+                .words()
+                .map(|word| word.text().to_string())
+                .collect()
+        })
+        .collect();
+    assert_eq!(commands, [vec!["ls", "-la"], vec!["grep", "\"a b\""]]);
+}
+
+#[test]
+fn command_redirects() {
+    let Expr::CommandExpr(command_expr) = parse_expr("$ sort < in.txt 2>&1 > \"out.txt\"") else {
+        panic!("expected a command expression");
+    };
+
+    let command = command_expr.commands().next().unwrap();
+    let redirects: Vec<_> = command
+        .redirects()
+        .map(|redirect| {
+            (
+                redirect.op().unwrap().kind(),
+                redirect.target().map(|target| target.text().to_string()),
+            )
+        })
+        .collect();
+    assert_eq!(
+        redirects,
+        [
+            (SyntaxKind::LAngle, Some("in.txt".to_string())),
+            (SyntaxKind::StderrToStdout, None),
+            (SyntaxKind::RAngle, Some("\"out.txt\"".to_string())),
+        ]
+    );
+    assert_eq!(
+        command
+            .words()
+            .map(|word| word.text().to_string())
+            .collect::<Vec<_>>(),
+        ["sort"]
+    );
+}
+
+#[test]
+fn string_interp_parts() {
+    let Expr::StringInterp(string) = parse_expr("\"a ${b} c ${d + 1}\"") else {
+        panic!("expected an interpolated string");
+    };
+
+    let parts: Vec<_> = string
+        .parts()
+        .map(|part| match part {
+            StringPart::Text(text) => format!("text {}", text.text()),
+            StringPart::Expr(expr) => format!("expr {}", expr.syntax().text()),
+        })
+        .collect();
+    assert_eq!(
+        parts,
+        ["text \"a ", "expr b", "text  c ", "expr d + 1", "text \""]
+    );
+}
@@ -0,0 +1,109 @@
+use psh_syntax::{SyntaxKind, SyntaxNode};
+
+use crate::{ast_node, child, children, AstNode, Path};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Type {
+    Named(NamedType),
+    Unit(UnitType),
+    Paren(ParenType),
+    Tuple(TupleType),
+    Array(ArrayType),
+    Map(MapType),
+    Function(FunctionType),
+}
+
+impl AstNode for Type {
+    fn cast(node: SyntaxNode) -> Option<Self> {
+        let ty = match node.kind() {
+            SyntaxKind::NamedType => Self::Named(NamedType(node)),
+            SyntaxKind::UnitType => Self::Unit(UnitType(node)),
+            SyntaxKind::ParenType => Self::Paren(ParenType(node)),
+            SyntaxKind::TupleType => Self::Tuple(TupleType(node)),
+            SyntaxKind::ArrayType => Self::Array(ArrayType(node)),
+            SyntaxKind::MapType => Self::Map(MapType(node)),
+            SyntaxKind::FunctionType => Self::Function(FunctionType(node)),
+            _ => return None,
+        };
+
+        Some(ty)
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        match self {
+            Self::Named(ty) => ty.syntax(),
+            Self::Unit(ty) => ty.syntax(),
+            Self::Paren(ty) => ty.syntax(),
+            Self::Tuple(ty) => ty.syntax(),
+            Self::Array(ty) => ty.syntax(),
+            Self::Map(ty) => ty.syntax(),
+            Self::Function(ty) => ty.syntax(),
+        }
+    }
+}
+
+ast_node!(TypeAnnotation);
+
+impl TypeAnnotation {
+    pub fn ty(&self) -> Option<Type> {
+        child(&self.0)
+    }
+}
+
+ast_node!(NamedType);
+
+impl NamedType {
+    pub fn path(&self) -> Option<Path> {
+        child(&self.0)
+    }
+}
+
+ast_node!(UnitType);
+
+ast_node!(ParenType);
+
+impl ParenType {
+    pub fn ty(&self) -> Option<Type> {
+        child(&self.0)
+    }
+}
+
+ast_node!(TupleType);
+
+impl TupleType {
+    pub fn types(&self) -> impl Iterator<Item = Type> {
+        children(&self.0)
+    }
+}
+
+ast_node!(ArrayType);
+
+impl ArrayType {
+    pub fn element(&self) -> Option<Type> {
+        child(&self.0)
+    }
+}
+
+ast_node!(MapType);
+
+impl MapType {
+    pub fn key(&self) -> Option<Type> {
+        children(&self.0).next()
+    }
+
+    pub fn value(&self) -> Option<Type> {
+        children(&self.0).nth(1)
+    }
+}
+
+ast_node!(FunctionType);
+
+impl FunctionType {
+    pub fn param(&self) -> Option<Type> {
+        children(&self.0).next()
+    }
+
+    pub fn ret(&self) -> Option<Type> {
+        children(&self.0).nth(1)
+    }
+}
@@ -0,0 +1,191 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, PipeReader, PipeWriter, Read};
+use std::process::{self, Child, ExitStatus, Stdio};
+use std::thread::{self, JoinHandle};
+
+use psh_hir::{Command, Redirect};
+
+use crate::{CommandOutput, EvalErrorKind, Number};
+
+/// Runs a pipeline of commands, connecting the stdout of each to the stdin of the next with an
+/// OS pipe.
+///
+/// The output of the pipeline is the stdout of its last command and the stderr of all of them,
+/// less whatever was redirected elsewhere. Its status is the status of the last command.
+pub(crate) fn run_pipeline(commands: &[Command]) -> Result<CommandOutput, EvalErrorKind> {
+    // Every command writes its stderr into the same pipe, which is drained on its own thread so
+    // that no command blocks on a full pipe while we are reading its stdout.
+    let (stderr_reader, stderr_writer) = io::pipe().map_err(|err| command_error(commands, err))?;
+    let stderr_reader = read_in_background(stderr_reader);
+
+    let mut children: Vec<Child> = Vec::with_capacity(commands.len());
+    let mut stdin = Stdio::inherit();
+    let mut stdout_reader = None;
+
+    for (idx, command) in commands.iter().enumerate() {
+        let (child, reader) = match spawn(command, stdin, &stderr_writer) {
+            Ok(spawned) => spawned,
+            Err(kind) => {
+                kill_all(children);
+                return Err(kind);
+            }
+        };
+
+        if idx + 1 < commands.len() {
+            stdin = Stdio::from(reader);
+        } else {
+            stdin = Stdio::null();
+            stdout_reader = Some(reader);
+        }
+        children.push(child);
+    }
+
+    // Our copy of the write end must be closed before reading, or the read never finishes.
+    drop(stderr_writer);
+
+    let mut stdout = Vec::new();
+    let read_stdout = stdout_reader
+        .expect("a pipeline has at least one command")
+        .read_to_end(&mut stdout);
+
+    let mut status = None;
+    for child in &mut children {
+        status = Some(child.wait());
+    }
+
+    let stderr = stderr_reader.join().unwrap_or_default();
+
+    read_stdout.map_err(|err| command_error(commands, err))?;
+    let status = status
+        .unwrap()
+        .map_err(|err| command_error(commands, err))?;
+
+    Ok(CommandOutput {
+        stdout: String::from_utf8_lossy(&stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&stderr).into_owned(),
+        status: exit_code(status),
+    })
+}
+
+/// Where a command's stdout or stderr is going.
+enum Output {
+    /// The pipe to the next command, or the pipeline's captured stdout for the last command.
+    Stdout,
+    /// The pipeline's captured stderr.
+    Stderr,
+    File(File),
+}
+
+/// Spawns a command with its redirections applied, returning the read end of its stdout.
+fn spawn(
+    command: &Command,
+    mut stdin: Stdio,
+    stderr_writer: &PipeWriter,
+) -> Result<(Child, PipeReader), EvalErrorKind> {
+    let mut stdout = Output::Stdout;
+    let mut stderr = Output::Stderr;
+
+    for redirect in &command.redirects {
+        match redirect {
+            Redirect::Stdin(path) => {
+                let file = File::open(path).map_err(|err| redirect_error(path, err))?;
+                stdin = Stdio::from(file);
+            }
+            Redirect::Stdout { path, append } => stdout = Output::File(create(path, *append)?),
+            Redirect::Stderr(path) => stderr = Output::File(create(path, false)?),
+            Redirect::StderrToStdout => {
+                stderr = match &stdout {
+                    Output::Stdout => Output::Stdout,
+                    Output::Stderr => Output::Stderr,
+                    Output::File(file) => {
+                        Output::File(file.try_clone().map_err(|err| error(command, err))?)
+                    }
+                };
+            }
+        }
+    }
+
+    let (stdout_reader, stdout_writer) = io::pipe().map_err(|err| error(command, err))?;
+    let to_stdio = |output: Output| -> io::Result<Stdio> {
+        Ok(match output {
+            Output::Stdout => Stdio::from(stdout_writer.try_clone()?),
+            Output::Stderr => Stdio::from(stderr_writer.try_clone()?),
+            Output::File(file) => Stdio::from(file),
+        })
+    };
+
+    let stdout = to_stdio(stdout).map_err(|err| error(command, err))?;
+    let stderr = to_stdio(stderr).map_err(|err| error(command, err))?;
+
+    let child = process::Command::new(&command.program)
+        .args(&command.args)
+        .stdin(stdin)
+        .stdout(stdout)
+        .stderr(stderr)
+        .spawn()
+        .map_err(|err| error(command, err))?;
+
+    Ok((child, stdout_reader))
+}
+
+fn create(path: &str, append: bool) -> Result<File, EvalErrorKind> {
+    OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(path)
+        .map_err(|err| redirect_error(path, err))
+}
+
+fn read_in_background(mut pipe: impl Read + Send + 'static) -> JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        buf
+    })
+}
+
+fn kill_all(children: Vec<Child>) {
+    for mut child in children {
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
+
+fn error(command: &Command, err: io::Error) -> EvalErrorKind {
+    EvalErrorKind::Command {
+        program: command.program.clone(),
+        message: err.to_string(),
+    }
+}
+
+fn command_error(commands: &[Command], err: io::Error) -> EvalErrorKind {
+    error(&commands[commands.len() - 1], err)
+}
+
+fn redirect_error(path: &str, err: io::Error) -> EvalErrorKind {
+    EvalErrorKind::Redirect {
+        path: path.to_string(),
+        message: err.to_string(),
+    }
+}
+
+/// The exit code of a process, following the shell convention of `128 + n` for a process
+/// killed by signal `n`.
+fn exit_code(status: ExitStatus) -> Number {
+    if let Some(code) = status.code() {
+        return Number::from(i64::from(code));
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+
+        if let Some(signal) = status.signal() {
+            return Number::from(128 + i64::from(signal));
+        }
+    }
+
+    Number::from(1)
+}
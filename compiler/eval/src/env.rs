@@ -0,0 +1,22 @@
+use std::collections::HashMap;
+
+use smol_str::SmolStr;
+
+use crate::Value;
+
+/// The values bound by `let`, kept between evaluations so later lines can refer to them.
+#[derive(Debug, Default, Clone)]
+pub struct Env {
+    bindings: HashMap<SmolStr, Value>,
+}
+
+impl Env {
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.bindings.get(name)
+    }
+
+    pub fn bind(&mut self, name: SmolStr, value: Value) {
+        self.bindings.insert(name, value);
+    }
+}
@@ -0,0 +1,350 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use psh_hir::{
+    BinaryOp, Database, Expr, ExprIdx, Literal, Pattern, PatternIdx, Stmt, StringPart, UnaryOp,
+};
+use smol_str::SmolStr;
+use text_size::TextRange;
+
+use crate::command::run_pipeline;
+use crate::{Env, EvalError, EvalErrorKind, Number, Value};
+
+/// Evaluates each statement in order, returning the value of the last one.
+///
+/// Bindings made by `let` are added to `env`, so they are visible to later calls.
+pub fn eval(db: &Database, stmts: &[Stmt], env: &mut Env) -> Result<Value, EvalError> {
+    let mut evaluator = Evaluator { db, env };
+
+    let mut value = Value::Unit;
+    for stmt in stmts {
+        value = evaluator.eval_stmt(stmt)?;
+    }
+
+    Ok(value)
+}
+
+struct Evaluator<'a> {
+    db: &'a Database,
+    env: &'a mut Env,
+}
+
+impl Evaluator<'_> {
+    fn eval_stmt(&mut self, stmt: &Stmt) -> Result<Value, EvalError> {
+        match stmt {
+            Stmt::ValueDef {
+                pattern,
+                params,
+                value,
+            } => {
+                let range = self.pattern_range(*pattern, TextRange::default());
+                if !params.is_empty() {
+                    return Err(error(EvalErrorKind::Unsupported("functions"), range));
+                }
+
+                let value = self.eval_expr(*value, range)?;
+
+                let mut bindings = vec![];
+                self.match_pattern(*pattern, value, range, &mut bindings)?;
+                for (name, value) in bindings {
+                    self.env.bind(name, value);
+                }
+
+                Ok(Value::Unit)
+            }
+            Stmt::Expr(expr) => self.eval_expr(*expr, TextRange::default()),
+        }
+    }
+
+    /// Evaluates an expression.
+    ///
+    /// Missing expressions have no range of their own, so errors about them use the range of
+    /// the expression enclosing them.
+    fn eval_expr(&mut self, idx: ExprIdx, enclosing: TextRange) -> Result<Value, EvalError> {
+        let range = self.db.source_map().expr_range(idx).unwrap_or(enclosing);
+
+        let value = match &self.db.exprs()[idx] {
+            Expr::Missing => return Err(error(EvalErrorKind::Missing, range)),
+            Expr::Literal(Literal::Integer(text) | Literal::Fraction(text)) => {
+                let n = Number::from_literal(text)
+                    .ok_or_else(|| error(EvalErrorKind::InvalidNumber(text.clone()), range))?;
+                Value::Number(n)
+            }
+            Expr::Literal(Literal::String(s)) => Value::String(s.clone()),
+            Expr::Literal(Literal::Bool(b)) => Value::Bool(*b),
+            Expr::StringInterp(parts) => {
+                let mut string = String::new();
+                for part in parts {
+                    match part {
+                        StringPart::Text(text) => string.push_str(text),
+                        StringPart::Expr(expr) => {
+                            let value = self.eval_expr(*expr, range)?;
+                            push_interpolated(&mut string, &value);
+                        }
+                    }
+                }
+                Value::String(string)
+            }
+            Expr::VariableRef { path } => {
+                let name = path.join("::");
+                match self.env.get(&name) {
+                    Some(value) => value.clone(),
+                    None => return Err(error(EvalErrorKind::UnboundVariable(name.into()), range)),
+                }
+            }
+            Expr::Unary { op, expr } => {
+                let value = self.eval_expr(*expr, range)?;
+                match (op, value) {
+                    (UnaryOp::Neg, Value::Number(n)) => Value::Number(-n),
+                    (UnaryOp::Not, value) => Value::Bool(!value.is_truthy()),
+                    (UnaryOp::Neg, value) => {
+                        let kind = EvalErrorKind::UnaryTypeMismatch {
+                            op: "-",
+                            operand: value.type_name(),
+                        };
+                        return Err(error(kind, range));
+                    }
+                }
+            }
+            // `and` and `or` short-circuit, so their right-hand side is only evaluated when it
+            // decides the result.
+            Expr::Binary {
+                op: op @ (BinaryOp::And | BinaryOp::Or),
+                lhs,
+                rhs,
+            } => {
+                let lhs = self.eval_expr(*lhs, range)?.is_truthy();
+                if lhs == (*op == BinaryOp::Or) {
+                    Value::Bool(lhs)
+                } else {
+                    Value::Bool(self.eval_expr(*rhs, range)?.is_truthy())
+                }
+            }
+            Expr::Binary { op, lhs, rhs } => {
+                let lhs = self.eval_expr(*lhs, range)?;
+                let rhs = self.eval_expr(*rhs, range)?;
+                eval_binary(*op, lhs, rhs, range)?
+            }
+            Expr::IfThenElse {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                if self.eval_expr(*condition, range)?.is_truthy() {
+                    self.eval_expr(*then_branch, range)?
+                } else {
+                    self.eval_expr(*else_branch, range)?
+                }
+            }
+            Expr::Unit => Value::Unit,
+            Expr::Tuple(elements) => Value::Tuple(self.eval_exprs(elements, range)?),
+            Expr::Array(elements) => Value::Array(self.eval_exprs(elements, range)?),
+            Expr::Map(entries) => {
+                let mut map = BTreeMap::new();
+                for (key, value) in entries {
+                    let Some(key) = key else {
+                        return Err(error(EvalErrorKind::Missing, range));
+                    };
+                    map.insert(key.clone(), self.eval_expr(*value, range)?);
+                }
+                Value::Map(map)
+            }
+            Expr::Index { base, index } => {
+                let base = self.eval_expr(*base, range)?;
+                let index = self.eval_expr(*index, range)?;
+                eval_index(base, index, range)?
+            }
+            Expr::Field { base, field } => {
+                let base = self.eval_expr(*base, range)?;
+                let Some(field) = field else {
+                    return Err(error(EvalErrorKind::Missing, range));
+                };
+                let value = match base {
+                    Value::Map(mut map) => map.remove(field),
+                    Value::CommandOutput(output) => output.field(field),
+                    _ => None,
+                };
+                match value {
+                    Some(value) => value,
+                    None => return Err(error(EvalErrorKind::UnknownField(field.clone()), range)),
+                }
+            }
+            Expr::Pipe { .. } => return Err(error(EvalErrorKind::Unsupported("pipes"), range)),
+            Expr::Call { .. } => {
+                return Err(error(EvalErrorKind::Unsupported("function calls"), range))
+            }
+            Expr::Lambda { .. } => return Err(error(EvalErrorKind::Unsupported("lambdas"), range)),
+            Expr::Command(commands) => match run_pipeline(commands) {
+                Ok(output) => Value::CommandOutput(output),
+                Err(kind) => return Err(error(kind, range)),
+            },
+        };
+
+        Ok(value)
+    }
+
+    fn eval_exprs(
+        &mut self,
+        exprs: &[ExprIdx],
+        enclosing: TextRange,
+    ) -> Result<Vec<Value>, EvalError> {
+        exprs
+            .iter()
+            .map(|expr| self.eval_expr(*expr, enclosing))
+            .collect()
+    }
+
+    /// Matches a value against a pattern, collecting the names it binds.
+    fn match_pattern(
+        &self,
+        idx: PatternIdx,
+        value: Value,
+        enclosing: TextRange,
+        bindings: &mut Vec<(SmolStr, Value)>,
+    ) -> Result<(), EvalError> {
+        let range = self.pattern_range(idx, enclosing);
+
+        match (&self.db.patterns()[idx], value) {
+            (Pattern::Missing, _) => Err(error(EvalErrorKind::Missing, range)),
+            (Pattern::Name(name), value) => {
+                bindings.push((name.clone(), value));
+                Ok(())
+            }
+            (Pattern::Unit, Value::Unit) => Ok(()),
+            (Pattern::Tuple(patterns), Value::Tuple(values)) if patterns.len() == values.len() => {
+                for (pattern, value) in patterns.iter().zip(values) {
+                    self.match_pattern(*pattern, value, range, bindings)?;
+                }
+                Ok(())
+            }
+            (pattern, value) => {
+                let kind = EvalErrorKind::PatternMismatch {
+                    expected: match pattern {
+                        Pattern::Tuple(patterns) => tuple_shape(patterns.len()),
+                        _ => "()".to_string(),
+                    },
+                    found: match value {
+                        Value::Tuple(values) => tuple_shape(values.len()),
+                        value => value.type_name().to_string(),
+                    },
+                };
+
+                Err(error(kind, range))
+            }
+        }
+    }
+
+    fn pattern_range(&self, idx: PatternIdx, enclosing: TextRange) -> TextRange {
+        self.db.source_map().pattern_range(idx).unwrap_or(enclosing)
+    }
+}
+
+fn eval_binary(op: BinaryOp, lhs: Value, rhs: Value, range: TextRange) -> Result<Value, EvalError> {
+    let value = match (op, lhs, rhs) {
+        (BinaryOp::Add, Value::Number(lhs), Value::Number(rhs)) => Value::Number(lhs + rhs),
+        (BinaryOp::Sub, Value::Number(lhs), Value::Number(rhs)) => Value::Number(lhs - rhs),
+        (BinaryOp::Mul, Value::Number(lhs), Value::Number(rhs)) => Value::Number(lhs * rhs),
+        (BinaryOp::Div, Value::Number(lhs), Value::Number(rhs)) => match lhs.checked_div(&rhs) {
+            Some(n) => Value::Number(n),
+            None => return Err(error(EvalErrorKind::DivisionByZero, range)),
+        },
+        (BinaryOp::Add, Value::String(lhs), Value::String(rhs)) => Value::String(lhs + &rhs),
+        (BinaryOp::Eq, lhs, rhs) => Value::Bool(lhs == rhs),
+        (BinaryOp::NotEq, lhs, rhs) => Value::Bool(lhs != rhs),
+        (
+            BinaryOp::Lt | BinaryOp::LtEq | BinaryOp::Gt | BinaryOp::GtEq,
+            Value::Number(lhs),
+            Value::Number(rhs),
+        ) => Value::Bool(is_ordered(op, lhs.cmp(&rhs))),
+        (
+            BinaryOp::Lt | BinaryOp::LtEq | BinaryOp::Gt | BinaryOp::GtEq,
+            Value::String(lhs),
+            Value::String(rhs),
+        ) => Value::Bool(is_ordered(op, lhs.cmp(&rhs))),
+        (op, lhs, rhs) => {
+            let kind = EvalErrorKind::BinaryTypeMismatch {
+                op: match op {
+                    BinaryOp::Add => "+",
+                    BinaryOp::Sub => "-",
+                    BinaryOp::Mul => "*",
+                    BinaryOp::Div => "/",
+                    BinaryOp::Eq => "==",
+                    BinaryOp::NotEq => "!=",
+                    BinaryOp::Lt => "<",
+                    BinaryOp::LtEq => "<=",
+                    BinaryOp::Gt => ">",
+                    BinaryOp::GtEq => ">=",
+                    BinaryOp::And => "and",
+                    BinaryOp::Or => "or",
+                },
+                lhs: lhs.type_name(),
+                rhs: rhs.type_name(),
+            };
+            return Err(error(kind, range));
+        }
+    };
+
+    Ok(value)
+}
+
+/// Whether two values ordered as `ordering` satisfy the comparison `op`.
+fn is_ordered(op: BinaryOp, ordering: Ordering) -> bool {
+    match op {
+        BinaryOp::Lt => ordering.is_lt(),
+        BinaryOp::LtEq => ordering.is_le(),
+        BinaryOp::Gt => ordering.is_gt(),
+        BinaryOp::GtEq => ordering.is_ge(),
+        _ => unreachable!("only comparisons order their operands"),
+    }
+}
+
+fn eval_index(base: Value, index: Value, range: TextRange) -> Result<Value, EvalError> {
+    match (base, index) {
+        (Value::Array(mut values), Value::Number(n)) => {
+            let len = values.len();
+            match n.to_index() {
+                Some(index) if index < len => Ok(values.swap_remove(index)),
+                _ => Err(error(
+                    EvalErrorKind::IndexOutOfBounds { index: n, len },
+                    range,
+                )),
+            }
+        }
+        (Value::Map(mut map), Value::String(key)) => match map.remove(key.as_str()) {
+            Some(value) => Ok(value),
+            None => Err(error(EvalErrorKind::UnknownField(key.into()), range)),
+        },
+        (base, index) => {
+            let kind = EvalErrorKind::InvalidIndex {
+                base: base.type_name(),
+                index: index.type_name(),
+            };
+            Err(error(kind, range))
+        }
+    }
+}
+
+/// Appends an interpolated value to a string.
+///
+/// Strings are added without their quotes, and commands add their stdout without its trailing
+/// newline, like `$(…)` in other shells.
+fn push_interpolated(string: &mut String, value: &Value) {
+    match value {
+        Value::String(s) => string.push_str(s),
+        Value::CommandOutput(output) => {
+            string.push_str(output.stdout.strip_suffix('\n').unwrap_or(&output.stdout));
+        }
+        value => {
+            let _ = write!(string, "{value}");
+        }
+    }
+}
+
+fn tuple_shape(len: usize) -> String {
+    format!("a tuple of {len}")
+}
+
+fn error(kind: EvalErrorKind, range: TextRange) -> EvalError {
+    EvalError { kind, range }
+}
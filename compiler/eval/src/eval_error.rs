@@ -0,0 +1,105 @@
+use std::fmt;
+
+use smol_str::SmolStr;
+use text_size::TextRange;
+
+use crate::Number;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvalError {
+    pub kind: EvalErrorKind,
+    pub range: TextRange,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvalErrorKind {
+    /// The parser recovered from an error here, so there is nothing to evaluate.
+    Missing,
+    UnboundVariable(SmolStr),
+    InvalidNumber(SmolStr),
+    DivisionByZero,
+    UnaryTypeMismatch {
+        op: &'static str,
+        operand: &'static str,
+    },
+    BinaryTypeMismatch {
+        op: &'static str,
+        lhs: &'static str,
+        rhs: &'static str,
+    },
+    PatternMismatch {
+        expected: String,
+        found: String,
+    },
+    IndexOutOfBounds {
+        index: Number,
+        len: usize,
+    },
+    InvalidIndex {
+        base: &'static str,
+        index: &'static str,
+    },
+    UnknownField(SmolStr),
+    /// An external command could not be started, or its output could not be read.
+    Command {
+        program: String,
+        message: String,
+    },
+    /// A file named in a redirection could not be opened.
+    Redirect {
+        path: String,
+        message: String,
+    },
+    Unsupported(&'static str),
+}
+
+impl fmt::Display for EvalErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalErrorKind::Missing => f.write_str("cannot evaluate an incomplete expression"),
+            EvalErrorKind::UnboundVariable(name) => write!(f, "‘{name}’ is not defined"),
+            EvalErrorKind::InvalidNumber(text) => write!(f, "‘{text}’ is not a valid number"),
+            EvalErrorKind::DivisionByZero => f.write_str("cannot divide by zero"),
+            EvalErrorKind::UnaryTypeMismatch { op, operand } => {
+                write!(f, "cannot apply ‘{op}’ to {operand}")
+            }
+            EvalErrorKind::BinaryTypeMismatch { op, lhs, rhs } => {
+                write!(f, "cannot apply ‘{op}’ to {lhs} and {rhs}")
+            }
+            EvalErrorKind::PatternMismatch { expected, found } => {
+                write!(f, "cannot match {found} against {expected}")
+            }
+            EvalErrorKind::IndexOutOfBounds { index, len } => {
+                write!(
+                    f,
+                    "index {index} is out of bounds for an array of length {len}"
+                )
+            }
+            EvalErrorKind::InvalidIndex { base, index } => {
+                write!(f, "cannot index into {base} with {index}")
+            }
+            EvalErrorKind::UnknownField(field) => write!(f, "no field named ‘{field}’"),
+            EvalErrorKind::Command { program, message } => {
+                write!(f, "cannot run ‘{program}’: {message}")
+            }
+            EvalErrorKind::Redirect { path, message } => {
+                write!(f, "cannot open ‘{path}’ for a redirection: {message}")
+            }
+            EvalErrorKind::Unsupported(what) => write!(f, "{what} cannot be evaluated yet"),
+        }
+    }
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "error in range {}..{}: {}",
+            u32::from(self.range.start()),
+            u32::from(self.range.end()),
+            self.kind,
+        )
+    }
+}
+
+impl std::error::Error for EvalError {}
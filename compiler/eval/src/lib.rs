@@ -0,0 +1,17 @@
+//! A tree-walking evaluator over the HIR.
+
+pub use crate::env::Env;
+pub use crate::eval::eval;
+pub use crate::eval_error::{EvalError, EvalErrorKind};
+pub use crate::number::{FractionStyle, Number};
+pub use crate::value::{CommandOutput, Value};
+
+mod command;
+mod env;
+mod eval;
+mod eval_error;
+mod number;
+mod value;
+
+#[cfg(test)]
+mod tests;
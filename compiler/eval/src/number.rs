@@ -0,0 +1,268 @@
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+
+/// An exact number.
+///
+/// Integers, decimals and fractions are all the same type, backed by an arbitrary-precision
+/// rational, so arithmetic never loses precision: `0.1 + 0.2` is exactly `0.3`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Number(BigRational);
+
+/// How to display a number whose decimal expansion never terminates, like `1/3`.
+///
+/// Integers and terminating decimals are always displayed exactly.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FractionStyle {
+    /// A reduced ratio, like `1/3`.
+    Ratio,
+    /// A decimal cut off after `digits` fractional digits and marked with an ellipsis, like
+    /// `0.3333…`.
+    Decimal { digits: usize },
+}
+
+impl Default for FractionStyle {
+    fn default() -> Self {
+        Self::Decimal { digits: 10 }
+    }
+}
+
+impl Number {
+    /// Parses the text of an integer or fraction literal, like `42` or `3.14`.
+    #[must_use]
+    pub fn from_literal(text: &str) -> Option<Self> {
+        let (whole, fractional) = text.split_once('.').unwrap_or((text, ""));
+        if whole.is_empty() || !is_digits(whole) || !is_digits(fractional) {
+            return None;
+        }
+
+        let numer: BigInt = format!("{whole}{fractional}").parse().ok()?;
+        let denom = BigInt::from(10).pow(u32::try_from(fractional.len()).ok()?);
+
+        Some(Self(BigRational::new(numer, denom)))
+    }
+
+    #[must_use]
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    #[must_use]
+    pub fn is_integer(&self) -> bool {
+        self.0.is_integer()
+    }
+
+    /// Divides by `rhs`, or returns `None` when `rhs` is zero.
+    #[must_use]
+    pub fn checked_div(&self, rhs: &Self) -> Option<Self> {
+        if rhs.is_zero() {
+            return None;
+        }
+
+        Some(Self(&self.0 / &rhs.0))
+    }
+
+    /// The number as an index into a collection, if it is a non-negative integer.
+    #[must_use]
+    pub fn to_index(&self) -> Option<usize> {
+        if !self.is_integer() {
+            return None;
+        }
+
+        self.0.to_integer().to_usize()
+    }
+
+    #[must_use]
+    pub fn display(&self, style: FractionStyle) -> impl fmt::Display + '_ {
+        Display {
+            number: self,
+            style,
+        }
+    }
+}
+
+impl From<i64> for Number {
+    fn from(n: i64) -> Self {
+        Self(BigRational::from_integer(n.into()))
+    }
+}
+
+impl Add for Number {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Number {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl Mul for Number {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self(self.0 * rhs.0)
+    }
+}
+
+impl Neg for Number {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display(FractionStyle::default()).fmt(f)
+    }
+}
+
+struct Display<'a> {
+    number: &'a Number,
+    style: FractionStyle,
+}
+
+impl fmt::Display for Display<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let n = &self.number.0;
+        if n.is_integer() {
+            return write!(f, "{}", n.numer());
+        }
+
+        let terminates = terminates(n.denom());
+        let max_digits = match self.style {
+            _ if terminates => None,
+            FractionStyle::Ratio => return write!(f, "{}/{}", n.numer(), n.denom()),
+            FractionStyle::Decimal { digits } => Some(digits),
+        };
+
+        if n.is_negative() {
+            f.write_str("-")?;
+        }
+
+        let numer = n.numer().abs();
+        let denom = n.denom();
+        let ten = BigInt::from(10);
+
+        write!(f, "{}.", &numer / denom)?;
+
+        let mut remainder = numer % denom;
+        let mut written = 0;
+        while !remainder.is_zero() {
+            if max_digits.is_some_and(|max_digits| written == max_digits) {
+                return f.write_str("…");
+            }
+
+            remainder *= &ten;
+            write!(f, "{}", &remainder / denom)?;
+            remainder %= denom;
+            written += 1;
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether a fraction with this (reduced) denominator has a finite decimal expansion, which
+/// is when its only prime factors are 2 and 5.
+fn terminates(denom: &BigInt) -> bool {
+    let mut denom = denom.clone();
+    for factor in [2, 5] {
+        let factor = BigInt::from(factor);
+        while (&denom % &factor).is_zero() {
+            denom /= &factor;
+        }
+    }
+
+    denom.is_one()
+}
+
+fn is_digits(text: &str) -> bool {
+    text.bytes().all(|b| b.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn number(text: &str) -> Number {
+        Number::from_literal(text).unwrap()
+    }
+
+    #[test]
+    fn parse_literals() {
+        assert_eq!(number("42"), Number::from(42));
+        assert_eq!(number("2.50"), number("2.5"));
+        assert_eq!(Number::from_literal("1.2.3"), None);
+        assert_eq!(Number::from_literal(".5"), None);
+    }
+
+    #[test]
+    fn decimal_arithmetic_is_exact() {
+        assert_eq!(number("0.1") + number("0.2"), number("0.3"));
+        assert_eq!(number("1") - number("0.9"), number("0.1"));
+        assert_eq!(number("0.1") * number("0.1"), number("0.01"));
+    }
+
+    #[test]
+    fn division_by_zero_is_none() {
+        assert_eq!(number("1").checked_div(&number("0.0")), None);
+        assert_eq!(number("1").checked_div(&number("4")), Some(number("0.25")));
+    }
+
+    #[test]
+    fn to_index() {
+        assert_eq!(number("3").to_index(), Some(3));
+        assert_eq!(number("3.5").to_index(), None);
+        assert_eq!((-number("3")).to_index(), None);
+    }
+
+    #[test]
+    fn display_integers_as_integers() {
+        assert_eq!(number("3.0").to_string(), "3");
+        assert_eq!((-number("12")).to_string(), "-12");
+    }
+
+    #[test]
+    fn display_terminating_fractions_exactly() {
+        assert_eq!(number("0.3").to_string(), "0.3");
+        assert_eq!((-number("1.25")).to_string(), "-1.25");
+        assert_eq!(number("0.000000000001").to_string(), "0.000000000001");
+    }
+
+    #[test]
+    fn display_non_terminating_fractions() {
+        let third = number("1").checked_div(&number("3")).unwrap();
+        let neg_two_thirds = -number("2").checked_div(&number("3")).unwrap();
+
+        assert_eq!(third.to_string(), "0.3333333333…");
+        assert_eq!(
+            third
+                .display(FractionStyle::Decimal { digits: 2 })
+                .to_string(),
+            "0.33…"
+        );
+        assert_eq!(third.display(FractionStyle::Ratio).to_string(), "1/3");
+        assert_eq!(
+            neg_two_thirds.display(FractionStyle::Ratio).to_string(),
+            "-2/3"
+        );
+        assert_eq!(
+            neg_two_thirds
+                .display(FractionStyle::Decimal { digits: 3 })
+                .to_string(),
+            "-0.666…"
+        );
+    }
+}
@@ -0,0 +1,452 @@
+use psh_ast::AstNode;
+use text_size::TextRange;
+
+use crate::*;
+
+fn eval_in(env: &mut Env, input: &str) -> Result<Value, EvalError> {
+    let parse = psh_parser::parse_repl_line(input);
+    assert_eq!(parse.errors(), &[], "{input} did not parse");
+
+    let source_file = psh_ast::SourceFile::cast(parse.syntax()).unwrap();
+    let (db, stmts) = psh_hir::lower(&source_file);
+
+    eval(&db, &stmts, env)
+}
+
+fn number(text: &str) -> Value {
+    Value::Number(Number::from_literal(text).unwrap())
+}
+
+fn check(input: &str, expected: Value) {
+    assert_eq!(eval_in(&mut Env::default(), input), Ok(expected));
+}
+
+fn check_error(input: &str, kind: EvalErrorKind, range: std::ops::Range<u32>) {
+    let expected = EvalError {
+        kind,
+        range: TextRange::new(range.start.into(), range.end.into()),
+    };
+
+    assert_eq!(eval_in(&mut Env::default(), input), Err(expected));
+}
+
+#[test]
+fn eval_literals() {
+    check("1", number("1"));
+    check("1.5", number("1.5"));
+    check("\"a\\nb\"", Value::String("a\nb".to_string()));
+    check("()", Value::Unit);
+}
+
+#[test]
+fn eval_arithmetic_respects_precedence() {
+    check("1 + 2 * 3", number("7"));
+    check("(1 + 2) * 3", number("9"));
+    check("10 - 4 / 2", number("8"));
+    check("-2 * 3", Value::Number(-Number::from(6)));
+}
+
+#[test]
+fn eval_string_concatenation() {
+    check("\"a\" + \"b\"", Value::String("ab".to_string()));
+}
+
+#[test]
+fn eval_string_interpolation() {
+    let mut env = Env::default();
+    eval_in(&mut env, "let name = \"psh\"").unwrap();
+
+    assert_eq!(
+        eval_in(&mut env, "\"hello ${name}, ${1 + 1} ${[1, 2]}\\n\""),
+        Ok(Value::String("hello psh, 2 [1, 2]\n".to_string()))
+    );
+    check(
+        "\"${\"in${\"ner\"}\"}\"",
+        Value::String("inner".to_string()),
+    );
+    check("\"${$ echo hi}!\"", Value::String("hi!".to_string()));
+}
+
+#[test]
+fn error_in_string_interpolation() {
+    check_error(
+        "\"a ${b}\"",
+        EvalErrorKind::UnboundVariable("b".into()),
+        5..6,
+    );
+}
+
+#[test]
+fn eval_if_then_else() {
+    check(
+        "if 1 then \"yes\" else \"no\"",
+        Value::String("yes".to_string()),
+    );
+    check(
+        "if 0 then \"yes\" else \"no\"",
+        Value::String("no".to_string()),
+    );
+}
+
+#[test]
+fn eval_only_the_taken_branch() {
+    check("if 1 then 2 else missing", number("2"));
+}
+
+#[test]
+fn eval_booleans() {
+    check("true", Value::Bool(true));
+    check("not false", Value::Bool(true));
+    check("not 0", Value::Bool(true));
+    check("if false then 1 else 2", number("2"));
+    check(
+        "(true, false)",
+        Value::Tuple(vec![Value::Bool(true), Value::Bool(false)]),
+    );
+}
+
+#[test]
+fn eval_comparisons() {
+    check("1 + 1 == 2", Value::Bool(true));
+    check("0.5 == 1 / 2", Value::Bool(true));
+    check("\"a\" != \"b\"", Value::Bool(true));
+    check("1 == \"1\"", Value::Bool(false));
+    check("[1, (2, 3)] == [1, (2, 3)]", Value::Bool(true));
+    check("1 < 2", Value::Bool(true));
+    check("2 <= 2", Value::Bool(true));
+    check("1.5 > 2", Value::Bool(false));
+    check("3 >= 2.5", Value::Bool(true));
+    check("\"apple\" < \"banana\"", Value::Bool(true));
+}
+
+#[test]
+fn eval_logical_operators() {
+    check("true and false", Value::Bool(false));
+    check("false or 1", Value::Bool(true));
+    check("1 < 2 and 2 < 3", Value::Bool(true));
+    check("not 1 == 2 and \"\" or ()", Value::Bool(false));
+}
+
+#[test]
+fn logical_operators_short_circuit() {
+    check("false and missing", Value::Bool(false));
+    check("true or missing", Value::Bool(true));
+    check_error(
+        "true and missing",
+        EvalErrorKind::UnboundVariable("missing".into()),
+        9..16,
+    );
+}
+
+#[test]
+fn error_on_comparing_mismatched_types() {
+    check_error(
+        "1 < \"2\"",
+        EvalErrorKind::BinaryTypeMismatch {
+            op: "<",
+            lhs: "Number",
+            rhs: "String",
+        },
+        0..7,
+    );
+}
+
+#[test]
+fn eval_tuple() {
+    check(
+        "(1, \"a\", ())",
+        Value::Tuple(vec![
+            number("1"),
+            Value::String("a".to_string()),
+            Value::Unit,
+        ]),
+    );
+}
+
+#[test]
+fn eval_array_index_and_map_field() {
+    check("[1, 2, 3][1]", number("2"));
+    check("{a: 1, \"b\": 2}.a", number("1"));
+    check("{a: 1, \"b\": 2}[\"b\"]", number("2"));
+}
+
+#[test]
+fn let_bindings_persist_in_env() {
+    let mut env = Env::default();
+
+    assert_eq!(eval_in(&mut env, "let a = 1 + 2"), Ok(Value::Unit));
+    assert_eq!(eval_in(&mut env, "a * 2"), Ok(number("6")));
+}
+
+#[test]
+fn let_destructures_tuples() {
+    let mut env = Env::default();
+
+    assert_eq!(
+        eval_in(&mut env, "let (a, (b, c)) = (1, (2, 3))"),
+        Ok(Value::Unit)
+    );
+    assert_eq!(eval_in(&mut env, "a + b + c"), Ok(number("6")));
+}
+
+#[test]
+fn failed_destructuring_binds_nothing() {
+    let mut env = Env::default();
+
+    assert!(eval_in(&mut env, "let (a, ()) = (1, 2)").is_err());
+    assert_eq!(env.get("a"), None);
+}
+
+#[test]
+fn error_on_unbound_variable() {
+    check_error("1 + a", EvalErrorKind::UnboundVariable("a".into()), 4..5);
+}
+
+#[test]
+fn error_on_mismatched_operands() {
+    check_error(
+        "1 + \"a\"",
+        EvalErrorKind::BinaryTypeMismatch {
+            op: "+",
+            lhs: "Number",
+            rhs: "String",
+        },
+        0..7,
+    );
+    check_error(
+        "-()",
+        EvalErrorKind::UnaryTypeMismatch {
+            op: "-",
+            operand: "Unit",
+        },
+        0..3,
+    );
+}
+
+#[test]
+fn error_on_pattern_mismatch() {
+    check_error(
+        "let (a, b) = (1, 2, 3)",
+        EvalErrorKind::PatternMismatch {
+            expected: "a tuple of 2".to_string(),
+            found: "a tuple of 3".to_string(),
+        },
+        4..10,
+    );
+}
+
+#[test]
+fn error_on_index_out_of_bounds() {
+    check_error(
+        "[1][1]",
+        EvalErrorKind::IndexOutOfBounds {
+            index: Number::from(1),
+            len: 1,
+        },
+        0..6,
+    );
+}
+
+#[test]
+fn error_on_incomplete_expression() {
+    let parse = psh_parser::parse_repl_line("1 +");
+    let source_file = psh_ast::SourceFile::cast(parse.syntax()).unwrap();
+    let (db, stmts) = psh_hir::lower(&source_file);
+
+    let error = eval(&db, &stmts, &mut Env::default()).unwrap_err();
+    assert_eq!(error.kind, EvalErrorKind::Missing);
+    assert_eq!(error.range, TextRange::new(0.into(), 3.into()));
+}
+
+#[test]
+fn display_values() {
+    let value = Value::Tuple(vec![
+        number("1"),
+        number("1.5"),
+        Value::String("a".to_string()),
+        Value::Array(vec![Value::Unit]),
+    ]);
+
+    assert_eq!(value.to_string(), "(1, 1.5, \"a\", [()])");
+    assert_eq!(Value::Tuple(vec![Value::Unit]).to_string(), "((),)");
+}
+
+#[test]
+fn eval_decimals_exactly() {
+    check("0.1 + 0.2", number("0.3"));
+    check("1 / 4", number("0.25"));
+}
+
+#[test]
+fn display_non_terminating_fractions() {
+    let third = eval_in(&mut Env::default(), "1 / 3").unwrap();
+
+    assert_eq!(third.to_string(), "0.3333333333…");
+    assert_eq!(third.display(FractionStyle::Ratio).to_string(), "1/3");
+}
+
+#[test]
+fn error_on_division_by_zero() {
+    check_error("1 / (2 - 2)", EvalErrorKind::DivisionByZero, 0..11);
+}
+
+fn command_output(input: &str) -> CommandOutput {
+    match eval_in(&mut Env::default(), input) {
+        Ok(Value::CommandOutput(output)) => output,
+        result => panic!("expected command output, found {result:?}"),
+    }
+}
+
+#[test]
+fn eval_command_captures_output() {
+    let output = command_output("$ sh -c 'echo out; echo err >&2; exit 3'");
+
+    assert_eq!(output.stdout, "out\n");
+    assert_eq!(output.stderr, "err\n");
+    assert_eq!(output.status, Number::from(3));
+    assert!(!Value::CommandOutput(output).is_truthy());
+}
+
+#[test]
+fn eval_command_fields() {
+    check("($ echo hi).stdout", Value::String("hi\n".to_string()));
+    check("($ true).status", number("0"));
+    check("if ($ false) then 1 else 2", number("2"));
+}
+
+#[test]
+fn eval_pipeline() {
+    let output = command_output("$ printf 'b\\na\\nc\\n' | sort | head -n 2");
+
+    assert_eq!(output.stdout, "a\nb\n");
+    assert!(output.succeeded());
+}
+
+#[test]
+fn pipeline_status_is_last_status() {
+    assert_eq!(command_output("$ false | true").status, Number::from(0));
+    assert_eq!(command_output("$ true | false").status, Number::from(1));
+}
+
+#[test]
+fn pipeline_streams_instead_of_buffering() {
+    // `yes` never ends on its own, so this only finishes if `head` reads from it while it runs.
+    let output = command_output("$ yes | head -n 3");
+
+    assert_eq!(output.stdout, "y\ny\ny\n");
+}
+
+#[test]
+fn error_on_unknown_command() {
+    let error = eval_in(&mut Env::default(), "$ psh-no-such-command arg").unwrap_err();
+
+    assert!(matches!(
+        error.kind,
+        EvalErrorKind::Command { program, .. } if program == "psh-no-such-command"
+    ));
+    assert_eq!(error.range, TextRange::new(0.into(), 25.into()));
+}
+
+fn command_output_in(dir: &tempfile::TempDir, input: &str) -> CommandOutput {
+    let input = input.replace("{dir}", &dir.path().display().to_string());
+    command_output(&input)
+}
+
+fn read(dir: &tempfile::TempDir, name: &str) -> String {
+    std::fs::read_to_string(dir.path().join(name)).unwrap()
+}
+
+#[test]
+fn redirect_stdout_to_file() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = command_output_in(&dir, "$ echo hi > \"{dir}/out.txt\"");
+    assert_eq!(output.stdout, "");
+    assert!(output.succeeded());
+    assert_eq!(read(&dir, "out.txt"), "hi\n");
+
+    command_output_in(&dir, "$ echo bye > \"{dir}/out.txt\"");
+    assert_eq!(read(&dir, "out.txt"), "bye\n");
+}
+
+#[test]
+fn redirect_stdout_appending_to_file() {
+    let dir = tempfile::tempdir().unwrap();
+
+    command_output_in(&dir, "$ echo one >> \"{dir}/out.txt\"");
+    command_output_in(&dir, "$ echo two >> \"{dir}/out.txt\"");
+    assert_eq!(read(&dir, "out.txt"), "one\ntwo\n");
+}
+
+#[test]
+fn redirect_stdin_from_file() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("in.txt"), "b\na\n").unwrap();
+
+    let output = command_output_in(&dir, "$ sort < \"{dir}/in.txt\"");
+    assert_eq!(output.stdout, "a\nb\n");
+}
+
+#[test]
+fn redirect_stderr_to_file() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = command_output_in(
+        &dir,
+        "$ sh -c 'echo out; echo err >&2' 2> \"{dir}/err.txt\"",
+    );
+    assert_eq!(output.stdout, "out\n");
+    assert_eq!(output.stderr, "");
+    assert_eq!(read(&dir, "err.txt"), "err\n");
+}
+
+#[test]
+fn redirect_stderr_to_stdout() {
+    let output = command_output("$ sh -c 'echo err >&2' 2>&1 | tr a-z A-Z");
+    assert_eq!(output.stdout, "ERR\n");
+    assert_eq!(output.stderr, "");
+}
+
+#[test]
+fn redirections_apply_in_order() {
+    let dir = tempfile::tempdir().unwrap();
+
+    // Like in other shells, `2>&1` only follows stdout to where it was going at that point.
+    let output = command_output_in(
+        &dir,
+        "$ sh -c 'echo out; echo err >&2' > \"{dir}/both.txt\" 2>&1",
+    );
+    assert_eq!((output.stdout.as_str(), output.stderr.as_str()), ("", ""));
+    assert_eq!(read(&dir, "both.txt"), "out\nerr\n");
+
+    let output = command_output_in(
+        &dir,
+        "$ sh -c 'echo out; echo err >&2' 2>&1 > \"{dir}/out.txt\"",
+    );
+    assert_eq!(
+        (output.stdout.as_str(), output.stderr.as_str()),
+        ("err\n", "")
+    );
+    assert_eq!(read(&dir, "out.txt"), "out\n");
+}
+
+#[test]
+fn redirected_stdout_is_not_piped() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = command_output_in(&dir, "$ echo hi > \"{dir}/out.txt\" | wc -c");
+    assert_eq!(output.stdout.trim(), "0");
+    assert_eq!(read(&dir, "out.txt"), "hi\n");
+}
+
+#[test]
+fn error_on_missing_redirect_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = format!("$ cat < \"{}/missing.txt\"", dir.path().display());
+
+    let error = eval_in(&mut Env::default(), &input).unwrap_err();
+    assert!(matches!(
+        error.kind,
+        EvalErrorKind::Redirect { path, .. } if path.ends_with("missing.txt")
+    ));
+}
@@ -0,0 +1,152 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use smol_str::SmolStr;
+
+use crate::{FractionStyle, Number};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Number(Number),
+    String(String),
+    Bool(bool),
+    Unit,
+    Tuple(Vec<Value>),
+    Array(Vec<Value>),
+    Map(BTreeMap<SmolStr, Value>),
+    CommandOutput(CommandOutput),
+}
+
+/// The result of running an external command.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandOutput {
+    pub stdout: String,
+    pub stderr: String,
+    pub status: Number,
+}
+
+impl CommandOutput {
+    /// Looks up one of the output’s fields: `stdout`, `stderr` or `status`.
+    #[must_use]
+    pub fn field(&self, name: &str) -> Option<Value> {
+        let value = match name {
+            "stdout" => Value::String(self.stdout.clone()),
+            "stderr" => Value::String(self.stderr.clone()),
+            "status" => Value::Number(self.status.clone()),
+            _ => return None,
+        };
+
+        Some(value)
+    }
+
+    #[must_use]
+    pub fn succeeded(&self) -> bool {
+        self.status.is_zero()
+    }
+}
+
+impl Value {
+    /// The name of the value’s type, as used in error messages.
+    #[must_use]
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Number(_) => "Number",
+            Self::String(_) => "String",
+            Self::Bool(_) => "Bool",
+            Self::Unit => "Unit",
+            Self::Tuple(_) => "Tuple",
+            Self::Array(_) => "Array",
+            Self::Map(_) => "Map",
+            Self::CommandOutput(_) => "CommandOutput",
+        }
+    }
+
+    /// Whether the value counts as true when used as a condition.
+    ///
+    /// `false`, zero, the empty string, unit, empty collections and failed commands are false;
+    /// everything else is true.
+    #[must_use]
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Self::Number(n) => !n.is_zero(),
+            Self::String(s) => !s.is_empty(),
+            Self::Bool(b) => *b,
+            Self::Unit => false,
+            Self::Tuple(values) | Self::Array(values) => !values.is_empty(),
+            Self::Map(entries) => !entries.is_empty(),
+            Self::CommandOutput(output) => output.succeeded(),
+        }
+    }
+
+    /// Displays the value, using `style` for any numbers that are non-terminating fractions.
+    #[must_use]
+    pub fn display(&self, style: FractionStyle) -> impl fmt::Display + '_ {
+        Display { value: self, style }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display(FractionStyle::default()).fmt(f)
+    }
+}
+
+struct Display<'a> {
+    value: &'a Value,
+    style: FractionStyle,
+}
+
+impl fmt::Display for Display<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let style = self.style;
+        match self.value {
+            Value::Number(n) => write!(f, "{}", n.display(style)),
+            Value::String(s) => write!(f, "{s:?}"),
+            Value::Bool(b) => write!(f, "{b}"),
+            Value::Unit => f.write_str("()"),
+            Value::Tuple(values) => {
+                f.write_str("(")?;
+                write_separated(f, values, style)?;
+                if values.len() == 1 {
+                    f.write_str(",")?;
+                }
+                f.write_str(")")
+            }
+            Value::Array(values) => {
+                f.write_str("[")?;
+                write_separated(f, values, style)?;
+                f.write_str("]")
+            }
+            Value::Map(entries) => {
+                f.write_str("{")?;
+                for (idx, (key, value)) in entries.iter().enumerate() {
+                    if idx > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{key}: {}", value.display(style))?;
+                }
+                f.write_str("}")
+            }
+            Value::CommandOutput(output) => write!(
+                f,
+                "{{stdout: {:?}, stderr: {:?}, status: {}}}",
+                output.stdout, output.stderr, output.status,
+            ),
+        }
+    }
+}
+
+fn write_separated(
+    f: &mut fmt::Formatter<'_>,
+    values: &[Value],
+    style: FractionStyle,
+) -> fmt::Result {
+    for (idx, value) in values.iter().enumerate() {
+        if idx > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{}", value.display(style))?;
+    }
+
+    Ok(())
+}
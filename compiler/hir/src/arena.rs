@@ -0,0 +1,250 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+
+/// An index into an [`Arena`].
+///
+/// Indexes are only meaningful for the arena that allocated them.
+pub struct Idx<T> {
+    raw: u32,
+    _ty: PhantomData<fn() -> T>,
+}
+
+impl<T> Idx<T> {
+    fn from_raw(raw: u32) -> Self {
+        Self {
+            raw,
+            _ty: PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub fn into_raw(self) -> u32 {
+        self.raw
+    }
+}
+
+impl<T> Clone for Idx<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Idx<T> {}
+
+impl<T> PartialEq for Idx<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.raw == other.raw
+    }
+}
+
+impl<T> Eq for Idx<T> {}
+
+/// Indexes are ordered by when their values were allocated.
+impl<T> PartialOrd for Idx<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Idx<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.raw.cmp(&other.raw)
+    }
+}
+
+impl<T> Hash for Idx<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.raw.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for Idx<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let type_name = std::any::type_name::<T>();
+        let type_name = type_name.rsplit("::").next().unwrap_or(type_name);
+
+        write!(f, "Idx::<{type_name}>({})", self.raw)
+    }
+}
+
+/// Owns values of a single type, handing out a copyable [`Idx`] for each one.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Arena<T> {
+    data: Vec<T>,
+}
+
+impl<T> Arena<T> {
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn alloc(&mut self, value: T) -> Idx<T> {
+        let idx = Idx::from_raw(u32::try_from(self.data.len()).expect("arena overflowed"));
+        self.data.push(value);
+        idx
+    }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (Idx<T>, &T)> {
+        self.data
+            .iter()
+            .enumerate()
+            .map(|(raw, value)| (Idx::from_raw(raw as u32), value))
+    }
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Self {
+        Self { data: Vec::new() }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Arena<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<T> Index<Idx<T>> for Arena<T> {
+    type Output = T;
+
+    fn index(&self, idx: Idx<T>) -> &T {
+        &self.data[idx.raw as usize]
+    }
+}
+
+impl<T> IndexMut<Idx<T>> for Arena<T> {
+    fn index_mut(&mut self, idx: Idx<T>) -> &mut T {
+        &mut self.data[idx.raw as usize]
+    }
+}
+
+/// Associates values with some of the indexes of an [`Arena`].
+#[derive(Clone, PartialEq, Eq)]
+pub struct ArenaMap<T, V> {
+    data: Vec<Option<V>>,
+    _ty: PhantomData<fn() -> T>,
+}
+
+impl<T, V> ArenaMap<T, V> {
+    pub fn insert(&mut self, idx: Idx<T>, value: V) {
+        let raw = idx.raw as usize;
+        if self.data.len() <= raw {
+            self.data.resize_with(raw + 1, || None);
+        }
+
+        self.data[raw] = Some(value);
+    }
+
+    #[must_use]
+    pub fn get(&self, idx: Idx<T>) -> Option<&V> {
+        self.data.get(idx.raw as usize)?.as_ref()
+    }
+}
+
+impl<T, V> Default for ArenaMap<T, V> {
+    fn default() -> Self {
+        Self {
+            data: Vec::new(),
+            _ty: PhantomData,
+        }
+    }
+}
+
+impl<T, V: fmt::Debug> fmt::Debug for ArenaMap<T, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entries = self
+            .data
+            .iter()
+            .enumerate()
+            .filter_map(|(raw, value)| Some((Idx::<T>::from_raw(raw as u32), value.as_ref()?)));
+
+        f.debug_map().entries(entries).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alloc_and_index() {
+        let mut arena = Arena::default();
+        let a = arena.alloc("a");
+        let b = arena.alloc("b");
+
+        assert_eq!(arena[a], "a");
+        assert_eq!(arena[b], "b");
+        assert_eq!(arena.len(), 2);
+    }
+
+    #[test]
+    fn indexes_are_ordered_by_allocation() {
+        let mut arena = Arena::default();
+        let a = arena.alloc("b");
+        let b = arena.alloc("a");
+        let c = arena.alloc("c");
+
+        assert!(a < b && b < c);
+        let order: Vec<_> = arena.iter().map(|(idx, _)| idx).collect();
+        assert_eq!(order, [a, b, c]);
+        assert_eq!(c.into_raw(), 2);
+    }
+
+    #[test]
+    fn map_only_holds_inserted_indexes() {
+        let mut arena = Arena::default();
+        let a = arena.alloc(1);
+        let b = arena.alloc(2);
+
+        let mut map = ArenaMap::default();
+        map.insert(b, "b");
+
+        assert_eq!(map.get(a), None);
+        assert_eq!(map.get(b), Some(&"b"));
+    }
+
+    #[test]
+    fn map_insert_overwrites_existing_value() {
+        let mut arena = Arena::default();
+        let a = arena.alloc(1);
+
+        let mut map = ArenaMap::default();
+        map.insert(a, "first");
+        map.insert(a, "second");
+
+        assert_eq!(map.get(a), Some(&"second"));
+    }
+
+    #[test]
+    fn map_grows_to_fit_later_indexes() {
+        let mut arena = Arena::default();
+        let indexes: Vec<_> = (0..5).map(|n| arena.alloc(n)).collect();
+
+        let mut map = ArenaMap::default();
+        map.insert(indexes[4], 4);
+        map.insert(indexes[1], 1);
+
+        let values: Vec<_> = indexes.iter().map(|idx| map.get(*idx).copied()).collect();
+        assert_eq!(values, [None, Some(1), None, None, Some(4)]);
+    }
+
+    #[test]
+    fn map_get_past_its_end_is_none() {
+        let mut arena = Arena::default();
+        let a = arena.alloc(1);
+        let b = arena.alloc(2);
+
+        let mut map = ArenaMap::default();
+        map.insert(a, "a");
+
+        assert_eq!(map.get(b), None);
+    }
+}
@@ -0,0 +1,388 @@
+use psh_ast::AstNode;
+use psh_syntax::{SyntaxElement, SyntaxKind, SyntaxToken};
+use text_size::TextRange;
+
+use crate::{
+    Arena, ArenaMap, BinaryOp, Command, Expr, ExprIdx, Literal, Pattern, PatternIdx, Redirect,
+    Stmt, StringPart, UnaryOp,
+};
+
+#[derive(Debug, Default)]
+pub struct Database {
+    exprs: Arena<Expr>,
+    patterns: Arena<Pattern>,
+    source_map: SourceMap,
+}
+
+/// Maps HIR ids back to the range of source they were lowered from.
+///
+/// Missing expressions and patterns have no source, so they have no range.
+#[derive(Debug, Default)]
+pub struct SourceMap {
+    exprs: ArenaMap<Expr, TextRange>,
+    patterns: ArenaMap<Pattern, TextRange>,
+}
+
+impl SourceMap {
+    #[must_use]
+    pub fn expr_range(&self, idx: ExprIdx) -> Option<TextRange> {
+        self.exprs.get(idx).copied()
+    }
+
+    #[must_use]
+    pub fn pattern_range(&self, idx: PatternIdx) -> Option<TextRange> {
+        self.patterns.get(idx).copied()
+    }
+}
+
+impl Database {
+    #[must_use]
+    pub fn exprs(&self) -> &Arena<Expr> {
+        &self.exprs
+    }
+
+    #[must_use]
+    pub fn patterns(&self) -> &Arena<Pattern> {
+        &self.patterns
+    }
+
+    #[must_use]
+    pub fn source_map(&self) -> &SourceMap {
+        &self.source_map
+    }
+
+    pub(crate) fn lower_stmt(&mut self, ast: psh_ast::Stmt) -> Option<Stmt> {
+        let stmt = match ast {
+            psh_ast::Stmt::ValueDef(ast) => Stmt::ValueDef {
+                pattern: self.lower_pattern(ast.pattern()),
+                params: self.lower_params(ast.param_list()),
+                value: self.lower_expr(ast.value()),
+            },
+            psh_ast::Stmt::TypeSignature(_) => return None,
+            psh_ast::Stmt::Expr(ast) => Stmt::Expr(self.lower_expr(Some(ast))),
+        };
+
+        Some(stmt)
+    }
+
+    fn lower_expr(&mut self, ast: Option<psh_ast::Expr>) -> ExprIdx {
+        let Some(ast) = ast else {
+            return self.exprs.alloc(Expr::Missing);
+        };
+
+        // Parentheses only group, so the inner expression stands in for them.
+        if let psh_ast::Expr::ParenExpr(ast) = ast {
+            return self.lower_expr(ast.expr());
+        }
+
+        let range = trimmed_range(ast.syntax().clone().into());
+        let expr = match ast {
+            psh_ast::Expr::IntLiteral(ast) => {
+                literal(ast.token(), |token| Literal::Integer(token.text().into()))
+            }
+            psh_ast::Expr::FractionLiteral(ast) => {
+                literal(ast.token(), |token| Literal::Fraction(token.text().into()))
+            }
+            psh_ast::Expr::StringLiteral(ast) => {
+                literal(ast.token(), |token| Literal::String(string_value(token)))
+            }
+            psh_ast::Expr::StringInterp(ast) => Expr::StringInterp(self.lower_string_parts(&ast)),
+            psh_ast::Expr::VariableRef(ast) => match ast.path() {
+                Some(path) => Expr::VariableRef {
+                    path: path
+                        .segments()
+                        .map(|segment| segment.text().into())
+                        .collect(),
+                },
+                None => Expr::Missing,
+            },
+            psh_ast::Expr::BoolLiteral(ast) => ast
+                .value()
+                .map_or(Expr::Missing, |value| Expr::Literal(Literal::Bool(value))),
+            psh_ast::Expr::UnaryExpr(ast) => {
+                let op = match ast.op().map(|op| op.kind()) {
+                    Some(SyntaxKind::Minus) => Some(UnaryOp::Neg),
+                    Some(SyntaxKind::NotKw) => Some(UnaryOp::Not),
+                    _ => None,
+                };
+
+                match op {
+                    Some(op) => Expr::Unary {
+                        op,
+                        expr: self.lower_expr(ast.expr()),
+                    },
+                    None => Expr::Missing,
+                }
+            }
+            psh_ast::Expr::InfixExpr(ast) => {
+                let op = match ast.op().map(|op| op.kind()) {
+                    Some(SyntaxKind::Plus) => BinaryOp::Add,
+                    Some(SyntaxKind::Minus) => BinaryOp::Sub,
+                    Some(SyntaxKind::Star) => BinaryOp::Mul,
+                    Some(SyntaxKind::Slash) => BinaryOp::Div,
+                    Some(SyntaxKind::DoubleEquals) => BinaryOp::Eq,
+                    Some(SyntaxKind::BangEquals) => BinaryOp::NotEq,
+                    Some(SyntaxKind::LAngle) => BinaryOp::Lt,
+                    Some(SyntaxKind::LAngleEquals) => BinaryOp::LtEq,
+                    Some(SyntaxKind::RAngle) => BinaryOp::Gt,
+                    Some(SyntaxKind::RAngleEquals) => BinaryOp::GtEq,
+                    Some(SyntaxKind::AndKw) => BinaryOp::And,
+                    Some(SyntaxKind::OrKw) => BinaryOp::Or,
+                    _ => unreachable!("infix expressions are only built around an operator"),
+                };
+
+                Expr::Binary {
+                    op,
+                    lhs: self.lower_expr(ast.lhs()),
+                    rhs: self.lower_expr(ast.rhs()),
+                }
+            }
+            psh_ast::Expr::PipeExpr(ast) => Expr::Pipe {
+                lhs: self.lower_expr(ast.lhs()),
+                rhs: self.lower_expr(ast.rhs()),
+            },
+            psh_ast::Expr::IfThenElseExpr(ast) => Expr::IfThenElse {
+                condition: self.lower_expr(ast.condition()),
+                then_branch: self.lower_expr(ast.then_branch()),
+                else_branch: self.lower_expr(ast.else_branch()),
+            },
+            psh_ast::Expr::Unit(_) => Expr::Unit,
+            psh_ast::Expr::ParenExpr(_) => unreachable!(),
+            psh_ast::Expr::TupleExpr(ast) => Expr::Tuple(self.lower_exprs(ast.elements())),
+            psh_ast::Expr::ArrayExpr(ast) => Expr::Array(self.lower_exprs(ast.elements())),
+            psh_ast::Expr::MapExpr(ast) => Expr::Map(
+                ast.entries()
+                    .map(|entry| {
+                        let key = entry.key().map(|key| match key.kind() {
+                            SyntaxKind::String => unquote(key.text()).into(),
+                            _ => key.text().into(),
+                        });
+
+                        (key, self.lower_expr(entry.value()))
+                    })
+                    .collect(),
+            ),
+            psh_ast::Expr::IndexExpr(ast) => Expr::Index {
+                base: self.lower_expr(ast.base()),
+                index: self.lower_expr(ast.index()),
+            },
+            psh_ast::Expr::FieldExpr(ast) => Expr::Field {
+                base: self.lower_expr(ast.base()),
+                field: ast.field().map(|field| field.text().into()),
+            },
+            psh_ast::Expr::CallExpr(ast) => Expr::Call {
+                callee: self.lower_expr(ast.callee()),
+                args: match ast.arg_list() {
+                    Some(arg_list) => self.lower_exprs(arg_list.args()),
+                    None => vec![],
+                },
+            },
+            psh_ast::Expr::LambdaExpr(ast) => Expr::Lambda {
+                params: self.lower_params(ast.param_list()),
+                body: self.lower_expr(ast.body()),
+            },
+            psh_ast::Expr::CommandExpr(ast) => ast
+                .commands()
+                .map(|command| lower_command(&command))
+                .collect::<Option<_>>()
+                .map_or(Expr::Missing, Expr::Command),
+        };
+
+        let is_missing = expr == Expr::Missing;
+        let idx = self.exprs.alloc(expr);
+        if !is_missing {
+            self.source_map.exprs.insert(idx, range);
+        }
+        idx
+    }
+
+    fn lower_exprs(&mut self, exprs: impl Iterator<Item = psh_ast::Expr>) -> Vec<ExprIdx> {
+        exprs.map(|expr| self.lower_expr(Some(expr))).collect()
+    }
+
+    fn lower_string_parts(&mut self, ast: &psh_ast::StringInterp) -> Vec<StringPart> {
+        let mut parts = vec![];
+        for part in ast.parts() {
+            match part {
+                psh_ast::StringPart::Text(token) => {
+                    let text = token.text();
+                    let text = match token.kind() {
+                        SyntaxKind::StringStart => &text[1..],
+                        SyntaxKind::StringEnd => &text[..text.len() - 1],
+                        _ => text,
+                    };
+                    if !text.is_empty() {
+                        parts.push(StringPart::Text(psh_lexer::unescape(text).0));
+                    }
+                }
+                psh_ast::StringPart::Expr(expr) => {
+                    parts.push(StringPart::Expr(self.lower_expr(Some(expr))));
+                }
+            }
+        }
+
+        parts
+    }
+
+    fn lower_params(&mut self, param_list: Option<psh_ast::ParamList>) -> Vec<PatternIdx> {
+        let Some(param_list) = param_list else {
+            return vec![];
+        };
+
+        param_list
+            .params()
+            .map(|param| self.lower_pattern(param.pattern()))
+            .collect()
+    }
+
+    fn lower_pattern(&mut self, ast: Option<psh_ast::Pattern>) -> PatternIdx {
+        let Some(ast) = ast else {
+            return self.patterns.alloc(Pattern::Missing);
+        };
+
+        // Like parenthesized expressions, parenthesized patterns only group.
+        if let psh_ast::Pattern::Paren(ast) = ast {
+            return self.lower_pattern(ast.pattern());
+        }
+
+        let range = trimmed_range(ast.syntax());
+        let pattern = match ast {
+            psh_ast::Pattern::Name(name) => Pattern::Name(name.text().into()),
+            psh_ast::Pattern::Unit(_) => Pattern::Unit,
+            psh_ast::Pattern::Paren(_) => unreachable!(),
+            psh_ast::Pattern::Tuple(ast) => Pattern::Tuple(
+                ast.args()
+                    .map(|arg| self.lower_pattern(arg.pattern()))
+                    .collect(),
+            ),
+        };
+
+        let idx = self.patterns.alloc(pattern);
+        self.source_map.patterns.insert(idx, range);
+        idx
+    }
+}
+
+/// The range of an element without the trivia the parser attached to its edges.
+fn trimmed_range(element: SyntaxElement) -> TextRange {
+    let node = match element {
+        SyntaxElement::Token(token) => return token.text_range(),
+        SyntaxElement::Node(node) => node,
+    };
+
+    let mut tokens = node
+        .descendants_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .filter(|token| !token.kind().is_trivia());
+
+    match (tokens.next(), tokens.last()) {
+        (Some(first), Some(last)) => first.text_range().cover(last.text_range()),
+        (Some(only), None) => only.text_range(),
+        _ => node.text_range(),
+    }
+}
+
+/// Lowers a command, or returns `None` if the parser could not find its name or the target of
+/// one of its redirections.
+fn lower_command(ast: &psh_ast::Command) -> Option<Command> {
+    let mut words = ast.words().map(|word| command_word(&word));
+
+    Some(Command {
+        program: words.next()?,
+        args: words.collect(),
+        redirects: ast
+            .redirects()
+            .map(|redirect| lower_redirect(&redirect))
+            .collect::<Option<_>>()?,
+    })
+}
+
+fn lower_redirect(ast: &psh_ast::Redirect) -> Option<Redirect> {
+    if ast.op()?.kind() == SyntaxKind::StderrToStdout {
+        return Some(Redirect::StderrToStdout);
+    }
+
+    let path = command_word(&ast.target()?);
+    let redirect = match ast.op()?.kind() {
+        SyntaxKind::LAngle => Redirect::Stdin(path),
+        SyntaxKind::RAngle => Redirect::Stdout {
+            path,
+            append: false,
+        },
+        SyntaxKind::DoubleRAngle => Redirect::Stdout { path, append: true },
+        SyntaxKind::StderrRAngle => Redirect::Stderr(path),
+        _ => unreachable!("redirections are only built around a redirection operator"),
+    };
+
+    Some(redirect)
+}
+
+fn command_word(word: &SyntaxToken) -> String {
+    match word.kind() {
+        SyntaxKind::String => unquote(word.text()),
+        _ => word.text().to_string(),
+    }
+}
+
+fn literal(token: Option<SyntaxToken>, f: impl FnOnce(&SyntaxToken) -> Literal) -> Expr {
+    token.map_or(Expr::Missing, |token| Expr::Literal(f(&token)))
+}
+
+/// The value of a plain, raw or multi-line string token.
+fn string_value(token: &SyntaxToken) -> String {
+    let text = token.text();
+    match token.kind() {
+        SyntaxKind::RawString => {
+            let hashes = text[1..].len() - text[1..].trim_start_matches('#').len();
+            text[hashes + 2..text.len() - hashes - 1].to_string()
+        }
+        SyntaxKind::MultilineString => {
+            psh_lexer::unescape(&trim_indent(&text[3..text.len() - 3])).0
+        }
+        _ => unquote(text),
+    }
+}
+
+/// Strips the indentation shared by the lines of a multi-line string, along with its first and
+/// last lines if they are blank, so that
+///
+/// ```text
+/// let s = """
+///     hello
+///       there
+///     """
+/// ```
+///
+/// is `"hello\n  there"`. Blank lines do not count towards the shared indentation.
+fn trim_indent(text: &str) -> String {
+    let is_blank = |line: &str| line.trim().is_empty();
+    let indent_len = |line: &str| line.len() - line.trim_start_matches([' ', '\t']).len();
+
+    let mut lines: Vec<&str> = text.split('\n').collect();
+    if lines.len() > 1 && lines.last().is_some_and(|line| is_blank(line)) {
+        lines.pop();
+    }
+    if lines.len() > 1 && is_blank(lines[0]) {
+        lines.remove(0);
+    }
+
+    let indent = lines
+        .iter()
+        .filter(|line| !is_blank(line))
+        .map(|line| indent_len(line))
+        .min()
+        .unwrap_or(0);
+
+    lines
+        .iter()
+        .map(|line| if is_blank(line) { "" } else { &line[indent..] })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Removes the quotes around a string token and resolves its escapes.
+///
+/// Malformed escapes are kept as they were written.
+fn unquote(text: &str) -> String {
+    psh_lexer::unescape(&text[1..text.len() - 1]).0
+}
@@ -0,0 +1,163 @@
+//! The high-level intermediate representation.
+//!
+//! Lowering strips trivia and error tokens from the syntax tree, leaving only what the program
+//! means. Anything the parser had to recover from becomes an explicit [`Expr::Missing`] or
+//! [`Pattern::Missing`], so later passes never have to deal with absent children.
+//!
+//! Types are not checked yet, so type signatures and annotations are not lowered.
+
+use smol_str::SmolStr;
+
+pub use crate::arena::{Arena, ArenaMap, Idx};
+pub use crate::database::{Database, SourceMap};
+
+mod arena;
+mod database;
+
+#[cfg(test)]
+mod tests;
+
+pub type ExprIdx = Idx<Expr>;
+pub type PatternIdx = Idx<Pattern>;
+
+#[must_use]
+pub fn lower(source_file: &psh_ast::SourceFile) -> (Database, Vec<Stmt>) {
+    let mut db = Database::default();
+    let stmts = source_file
+        .stmts()
+        .filter_map(|stmt| db.lower_stmt(stmt))
+        .collect();
+
+    (db, stmts)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Stmt {
+    ValueDef {
+        pattern: PatternIdx,
+        params: Vec<PatternIdx>,
+        value: ExprIdx,
+    },
+    Expr(ExprIdx),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Missing,
+    Literal(Literal),
+    /// A string with interpolations, like `"hello ${name}"`.
+    StringInterp(Vec<StringPart>),
+    VariableRef {
+        path: Vec<SmolStr>,
+    },
+    Unary {
+        op: UnaryOp,
+        expr: ExprIdx,
+    },
+    Binary {
+        op: BinaryOp,
+        lhs: ExprIdx,
+        rhs: ExprIdx,
+    },
+    Pipe {
+        lhs: ExprIdx,
+        rhs: ExprIdx,
+    },
+    IfThenElse {
+        condition: ExprIdx,
+        then_branch: ExprIdx,
+        else_branch: ExprIdx,
+    },
+    Unit,
+    Tuple(Vec<ExprIdx>),
+    Array(Vec<ExprIdx>),
+    Map(Vec<(Option<SmolStr>, ExprIdx)>),
+    Index {
+        base: ExprIdx,
+        index: ExprIdx,
+    },
+    Field {
+        base: ExprIdx,
+        field: Option<SmolStr>,
+    },
+    Call {
+        callee: ExprIdx,
+        args: Vec<ExprIdx>,
+    },
+    Lambda {
+        params: Vec<PatternIdx>,
+        body: ExprIdx,
+    },
+    /// External commands, with the output of each piped into the next.
+    Command(Vec<Command>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Command {
+    pub program: String,
+    pub args: Vec<String>,
+    /// Redirections of the command's input and output, applied in order.
+    pub redirects: Vec<Redirect>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Redirect {
+    /// `< path`
+    Stdin(String),
+    /// `> path`, or `>> path` when `append` is set.
+    Stdout { path: String, append: bool },
+    /// `2> path`
+    Stderr(String),
+    /// `2>&1`, which sends stderr wherever stdout is going at that point.
+    StderrToStdout,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Literal {
+    /// The digits of an integer literal, exactly as written.
+    Integer(SmolStr),
+    /// The digits of a fraction literal, exactly as written.
+    Fraction(SmolStr),
+    /// The contents of a string literal, with quotes removed and escapes resolved.
+    String(String),
+    Bool(bool),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StringPart {
+    /// Text between interpolations, with escapes resolved.
+    Text(String),
+    Expr(ExprIdx),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum UnaryOp {
+    Neg,
+    Not,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+    /// Only evaluates its right-hand side if its left-hand side is true.
+    And,
+    /// Only evaluates its right-hand side if its left-hand side is false.
+    Or,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pattern {
+    Missing,
+    Name(SmolStr),
+    Unit,
+    Tuple(Vec<PatternIdx>),
+}
@@ -0,0 +1,383 @@
+use std::fs;
+use std::path::Path;
+
+use psh_ast::AstNode;
+use psh_syntax::{PshLanguage, SyntaxKind};
+use rowan::Language;
+use text_size::TextRange;
+
+use crate::*;
+
+fn parse(input: &str) -> (Database, Vec<Stmt>) {
+    let parse = psh_parser::parse_repl_line(input);
+    let source_file = psh_ast::SourceFile::cast(parse.syntax()).unwrap();
+
+    lower(&source_file)
+}
+
+fn lower_expr(input: &str) -> (Database, ExprIdx) {
+    let (db, stmts) = parse(input);
+    match stmts.as_slice() {
+        [Stmt::Expr(expr)] => (db, *expr),
+        _ => panic!("expected a single expression, found {stmts:?}"),
+    }
+}
+
+fn range(range: std::ops::Range<u32>) -> Option<TextRange> {
+    Some(TextRange::new(range.start.into(), range.end.into()))
+}
+
+#[test]
+fn lower_binary_expr() {
+    let (db, expr) = lower_expr("1 + a");
+
+    let Expr::Binary { op, lhs, rhs } = &db.exprs()[expr] else {
+        panic!("expected a binary expression");
+    };
+    assert_eq!(*op, BinaryOp::Add);
+    assert_eq!(
+        db.exprs()[*lhs],
+        Expr::Literal(Literal::Integer("1".into()))
+    );
+    assert_eq!(
+        db.exprs()[*rhs],
+        Expr::VariableRef {
+            path: vec!["a".into()]
+        }
+    );
+}
+
+#[test]
+fn lower_binary_expr_without_rhs() {
+    let (db, expr) = lower_expr("1 +");
+
+    let Expr::Binary { rhs, .. } = &db.exprs()[expr] else {
+        panic!("expected a binary expression");
+    };
+    assert_eq!(db.exprs()[*rhs], Expr::Missing);
+    assert_eq!(db.source_map().expr_range(*rhs), None);
+}
+
+#[test]
+fn lower_unary_expr_without_operator_to_missing() {
+    // The parser always gives a unary expression its operator, so the tree is built by hand.
+    let mut builder = rowan::GreenNodeBuilder::new();
+    for kind in [
+        SyntaxKind::SourceFile,
+        SyntaxKind::UnaryExpr,
+        SyntaxKind::IntLiteral,
+    ] {
+        builder.start_node(PshLanguage::kind_to_raw(kind));
+    }
+    builder.token(PshLanguage::kind_to_raw(SyntaxKind::Integer), "1");
+    for _ in 0..3 {
+        builder.finish_node();
+    }
+    let root = psh_syntax::SyntaxNode::new_root(builder.finish());
+    let (db, stmts) = lower(&psh_ast::SourceFile::cast(root).unwrap());
+
+    let [Stmt::Expr(expr)] = stmts.as_slice() else {
+        panic!("expected a single expression, found {stmts:?}");
+    };
+    assert_eq!(db.exprs()[*expr], Expr::Missing);
+}
+
+#[test]
+fn lower_logical_and_comparison_operators() {
+    let (db, expr) = lower_expr("not a >= 1 or true");
+
+    let Expr::Binary { op, lhs, rhs } = &db.exprs()[expr] else {
+        panic!("expected a binary expression");
+    };
+    assert_eq!(*op, BinaryOp::Or);
+    assert_eq!(db.exprs()[*rhs], Expr::Literal(Literal::Bool(true)));
+
+    let Expr::Unary { op, expr } = &db.exprs()[*lhs] else {
+        panic!("expected a unary expression");
+    };
+    assert_eq!(*op, UnaryOp::Not);
+    assert!(matches!(
+        db.exprs()[*expr],
+        Expr::Binary {
+            op: BinaryOp::GtEq,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn lower_if_then_else_without_else_branch() {
+    let (db, expr) = lower_expr("if a then b");
+
+    let Expr::IfThenElse { else_branch, .. } = &db.exprs()[expr] else {
+        panic!("expected an if-then-else expression");
+    };
+    assert_eq!(db.exprs()[*else_branch], Expr::Missing);
+}
+
+#[test]
+fn lower_paren_expr_to_inner_expr() {
+    let (db, expr) = lower_expr("(1)");
+
+    assert_eq!(
+        db.exprs()[expr],
+        Expr::Literal(Literal::Integer("1".into()))
+    );
+    assert_eq!(db.source_map().expr_range(expr), range(1..2));
+}
+
+#[test]
+fn lower_string_literal_without_quotes() {
+    let (db, expr) = lower_expr(r#""a\tb\"c""#);
+
+    assert_eq!(
+        db.exprs()[expr],
+        Expr::Literal(Literal::String("a\tb\"c".to_string()))
+    );
+}
+
+#[test]
+fn lower_string_literal_escapes() {
+    let (db, expr) = lower_expr(r"'\\ \' \r\0 \u{1F600} \q'");
+
+    assert_eq!(
+        db.exprs()[expr],
+        Expr::Literal(Literal::String("\\ ' \r\0 😀 \\q".to_string()))
+    );
+}
+
+#[test]
+fn lower_raw_string_literal() {
+    let (db, expr) = lower_expr(r###"r##"C:\path "# \n"##"###);
+
+    assert_eq!(
+        db.exprs()[expr],
+        Expr::Literal(Literal::String(r##"C:\path "# \n"##.to_string()))
+    );
+}
+
+#[test]
+fn lower_multiline_string_literal() {
+    let (db, expr) = lower_expr("\"\"\"\n    hello\n\n      \\\"there\\\"\\t\n    \"\"\"");
+
+    assert_eq!(
+        db.exprs()[expr],
+        Expr::Literal(Literal::String("hello\n\n  \"there\"\t".to_string()))
+    );
+}
+
+#[test]
+fn lower_multiline_string_on_one_line() {
+    let (db, expr) = lower_expr("\"\"\"  a \"quote\" \"\"\"");
+
+    assert_eq!(
+        db.exprs()[expr],
+        Expr::Literal(Literal::String("a \"quote\" ".to_string()))
+    );
+}
+
+#[test]
+fn lower_tuple_expr() {
+    let (db, expr) = lower_expr("(1, 2.5, ())");
+
+    let Expr::Tuple(elements) = &db.exprs()[expr] else {
+        panic!("expected a tuple expression");
+    };
+    let elements: Vec<_> = elements
+        .iter()
+        .map(|element| &db.exprs()[*element])
+        .collect();
+    assert_eq!(
+        elements,
+        [
+            &Expr::Literal(Literal::Integer("1".into())),
+            &Expr::Literal(Literal::Fraction("2.5".into())),
+            &Expr::Unit,
+        ]
+    );
+}
+
+#[test]
+fn lower_map_entry_without_key() {
+    let (db, expr) = lower_expr("{: 1, b: 2}");
+
+    let Expr::Map(entries) = &db.exprs()[expr] else {
+        panic!("expected a map expression");
+    };
+    let keys: Vec<_> = entries.iter().map(|(key, _)| key.as_deref()).collect();
+    assert_eq!(keys, [None, Some("b")]);
+
+    let (_, value) = entries[0];
+    assert_eq!(
+        db.exprs()[value],
+        Expr::Literal(Literal::Integer("1".into()))
+    );
+    assert_eq!(db.source_map().expr_range(value), range(3..4));
+}
+
+#[test]
+fn lower_value_def() {
+    let (db, stmts) = parse("let (a, b) = (1, 2)");
+
+    let [Stmt::ValueDef {
+        pattern,
+        params,
+        value,
+    }] = stmts.as_slice()
+    else {
+        panic!("expected a value definition, found {stmts:?}");
+    };
+    assert!(params.is_empty());
+    assert!(matches!(db.exprs()[*value], Expr::Tuple(_)));
+
+    let Pattern::Tuple(names) = &db.patterns()[*pattern] else {
+        panic!("expected a tuple pattern");
+    };
+    let names: Vec<_> = names.iter().map(|name| &db.patterns()[*name]).collect();
+    assert_eq!(
+        names,
+        [&Pattern::Name("a".into()), &Pattern::Name("b".into())]
+    );
+    assert_eq!(db.source_map().pattern_range(*pattern), range(4..10));
+}
+
+#[test]
+fn lower_value_def_without_value() {
+    let (db, stmts) = parse("let a =");
+
+    let [Stmt::ValueDef { pattern, value, .. }] = stmts.as_slice() else {
+        panic!("expected a value definition, found {stmts:?}");
+    };
+    assert_eq!(db.patterns()[*pattern], Pattern::Name("a".into()));
+    assert_eq!(db.exprs()[*value], Expr::Missing);
+}
+
+#[test]
+fn lower_type_signature_to_nothing() {
+    let (_db, stmts) = parse("add : Number -> Number");
+
+    assert!(stmts.is_empty());
+}
+
+#[test]
+fn source_map_points_into_input() {
+    let (db, expr) = lower_expr("foo [1, 2]");
+
+    let Expr::Call { callee, args } = &db.exprs()[expr] else {
+        panic!("expected a call expression");
+    };
+    assert_eq!(db.source_map().expr_range(expr), range(0..10));
+    assert_eq!(db.source_map().expr_range(*callee), range(0..3));
+    assert_eq!(db.source_map().expr_range(args[0]), range(4..10));
+}
+
+#[test]
+fn every_fixture_lowers() {
+    let tests_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../parser/src/tests/repl_line");
+
+    for entry in fs::read_dir(tests_dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_none_or(|ext| ext != "test") {
+            continue;
+        }
+
+        let test_content = fs::read_to_string(&path).unwrap();
+        let (input, _expected) = test_content.split_once("\n===\n").unwrap();
+        let (db, _stmts) = parse(input);
+
+        for (idx, expr) in db.exprs().iter() {
+            let range = db.source_map().expr_range(idx);
+            assert_eq!(
+                range.is_none(),
+                *expr == Expr::Missing,
+                "{path:?}: {expr:?} has range {range:?}",
+            );
+        }
+    }
+}
+
+#[test]
+fn lower_command_pipeline() {
+    let (db, expr) = lower_expr("$ ls -la | grep 'a b'");
+
+    assert_eq!(
+        db.exprs()[expr],
+        Expr::Command(vec![
+            Command {
+                program: "ls".to_string(),
+                args: vec!["-la".to_string()],
+                redirects: vec![],
+            },
+            Command {
+                program: "grep".to_string(),
+                args: vec!["a b".to_string()],
+                redirects: vec![],
+            },
+        ])
+    );
+}
+
+#[test]
+fn lower_command_without_name() {
+    let (db, expr) = lower_expr("$ ls |");
+
+    assert_eq!(db.exprs()[expr], Expr::Missing);
+}
+
+#[test]
+fn lower_command_redirects() {
+    let (db, expr) = lower_expr("$ sort < in.txt >> 'out file' 2> err.txt 2>&1");
+
+    assert_eq!(
+        db.exprs()[expr],
+        Expr::Command(vec![Command {
+            program: "sort".to_string(),
+            args: vec![],
+            redirects: vec![
+                Redirect::Stdin("in.txt".to_string()),
+                Redirect::Stdout {
+                    path: "out file".to_string(),
+                    append: true,
+                },
+                Redirect::Stderr("err.txt".to_string()),
+                Redirect::StderrToStdout,
+            ],
+        }])
+    );
+}
+
+#[test]
+fn lower_command_without_redirect_target() {
+    let (db, expr) = lower_expr("$ ls >");
+
+    assert_eq!(db.exprs()[expr], Expr::Missing);
+}
+
+#[test]
+fn lower_string_interp() {
+    let (db, expr) = lower_expr("\"a\\t${b}${c}\\\"\"");
+
+    let Expr::StringInterp(parts) = &db.exprs()[expr] else {
+        panic!(
+            "expected an interpolated string, found {:?}",
+            db.exprs()[expr]
+        );
+    };
+    let [StringPart::Text(a), StringPart::Expr(b), StringPart::Expr(c), StringPart::Text(d)] =
+        parts.as_slice()
+    else {
+        panic!("unexpected parts {parts:?}");
+    };
+
+    assert_eq!((a.as_str(), d.as_str()), ("a\t", "\""));
+    assert_eq!(
+        db.exprs()[*b],
+        Expr::VariableRef {
+            path: vec!["b".into()]
+        }
+    );
+    assert_eq!(
+        db.source_map().expr_range(*c),
+        Some(TextRange::new(10.into(), 11.into()))
+    );
+}
@@ -0,0 +1,215 @@
+use crate::TokenKind;
+
+/// Lexes the next token of a command, like the `git status` in `$ git status`.
+///
+/// Commands are lexed as shell words rather than psh tokens, so that `--force` or `*.txt` are
+/// plain arguments. Redirections like `> out.txt` or `2>&1` are lexed as operators.
+///
+/// Returns `None` at the end of the command: a newline, `;`, `)` or the end of the input. A
+/// pipeline can continue onto the next line after a `|`, so newlines are whitespace when
+/// `after_pipe` is set. A command inside an interpolation also ends at a `}`, which is set by
+/// `in_interpolation`.
+pub(crate) fn lex_command_token(
+    remainder: &str,
+    after_pipe: bool,
+    in_interpolation: bool,
+) -> Option<(TokenKind, usize)> {
+    let first = remainder.chars().next()?;
+    let is_word_end = |c| ends_word(c) || (in_interpolation && c == '}');
+
+    let token = match first {
+        '\n' if after_pipe => (
+            TokenKind::Whitespace,
+            len_while(remainder, char::is_whitespace),
+        ),
+        '\n' | ';' | ')' => return None,
+        '}' if in_interpolation => return None,
+        ' ' | '\t' | '\u{c}' | '\r' => (
+            TokenKind::Whitespace,
+            len_while(remainder, |c| matches!(c, ' ' | '\t' | '\u{c}' | '\r')),
+        ),
+        '|' => (TokenKind::Pipe, 1),
+        _ if remainder.starts_with("2>&1") => (TokenKind::StderrToStdout, 4),
+        _ if remainder.starts_with("2>") => (TokenKind::StderrRAngle, 2),
+        _ if remainder.starts_with(">>") => (TokenKind::DoubleRAngle, 2),
+        '>' => (TokenKind::RAngle, 1),
+        '<' => (TokenKind::LAngle, 1),
+        '"' | '\'' => lex_quoted(remainder, first),
+        _ => (
+            TokenKind::CommandWord,
+            len_while(remainder, |c| !is_word_end(c)),
+        ),
+    };
+
+    Some(token)
+}
+
+fn ends_word(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '|' | ';' | ')' | '"' | '\'' | '<' | '>')
+}
+
+fn lex_quoted(remainder: &str, quote: char) -> (TokenKind, usize) {
+    let mut escaped = false;
+    for (idx, c) in remainder.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            _ if c == quote => return (TokenKind::String, idx + c.len_utf8()),
+            _ => {}
+        }
+    }
+
+    // An unterminated string swallows the rest of its line.
+    (TokenKind::Error, len_while(remainder, |c| c != '\n'))
+}
+
+fn len_while(text: &str, f: impl Fn(char) -> bool) -> usize {
+    text.find(|c| !f(c)).unwrap_or(text.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    fn check(input: &str, expected: &[(TokenKind, &str)]) {
+        let mut remainder = input;
+        let mut actual = vec![];
+        let mut after_pipe = false;
+        while let Some((kind, len)) = lex_command_token(remainder, after_pipe, false) {
+            actual.push((kind, &remainder[..len]));
+            remainder = &remainder[len..];
+            after_pipe = kind == TokenKind::Pipe || (after_pipe && kind == TokenKind::Whitespace);
+        }
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn lex_words() {
+        check(
+            "git commit --amend -m",
+            &[
+                (TokenKind::CommandWord, "git"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::CommandWord, "commit"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::CommandWord, "--amend"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::CommandWord, "-m"),
+            ],
+        );
+    }
+
+    #[test]
+    fn lex_quoted_words() {
+        check(
+            r#"echo "a \"b\" c" 'd'"#,
+            &[
+                (TokenKind::CommandWord, "echo"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::String, r#""a \"b\" c""#),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::String, "'d'"),
+            ],
+        );
+    }
+
+    #[test]
+    fn lex_unterminated_quote() {
+        check(
+            "echo \"abc\nls",
+            &[
+                (TokenKind::CommandWord, "echo"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::Error, "\"abc"),
+            ],
+        );
+    }
+
+    #[test]
+    fn lex_pipes() {
+        check(
+            "ls|wc -l",
+            &[
+                (TokenKind::CommandWord, "ls"),
+                (TokenKind::Pipe, "|"),
+                (TokenKind::CommandWord, "wc"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::CommandWord, "-l"),
+            ],
+        );
+    }
+
+    #[test]
+    fn lex_pipe_at_end_of_line() {
+        check(
+            "ls |\n  wc\nfoo",
+            &[
+                (TokenKind::CommandWord, "ls"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::Pipe, "|"),
+                (TokenKind::Whitespace, "\n  "),
+                (TokenKind::CommandWord, "wc"),
+            ],
+        );
+    }
+
+    #[test]
+    fn lex_redirections() {
+        check(
+            "sort <in.txt >>out.txt 2>err.txt 2>&1",
+            &[
+                (TokenKind::CommandWord, "sort"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::LAngle, "<"),
+                (TokenKind::CommandWord, "in.txt"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::DoubleRAngle, ">>"),
+                (TokenKind::CommandWord, "out.txt"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::StderrRAngle, "2>"),
+                (TokenKind::CommandWord, "err.txt"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::StderrToStdout, "2>&1"),
+            ],
+        );
+    }
+
+    #[test]
+    fn lex_redirection_ends_word() {
+        check(
+            "echo a2>b 2",
+            &[
+                (TokenKind::CommandWord, "echo"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::CommandWord, "a2"),
+                (TokenKind::RAngle, ">"),
+                (TokenKind::CommandWord, "b"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::CommandWord, "2"),
+            ],
+        );
+    }
+
+    #[test]
+    fn command_ends_at_brace_in_interpolation() {
+        assert_eq!(
+            lex_command_token("whoami}\"", false, true),
+            Some((TokenKind::CommandWord, 6))
+        );
+        assert_eq!(lex_command_token("}\"", false, true), None);
+        assert_eq!(
+            lex_command_token("{}", false, false),
+            Some((TokenKind::CommandWord, 2))
+        );
+    }
+
+    #[test]
+    fn command_ends() {
+        check("ls\nfoo", &[(TokenKind::CommandWord, "ls")]);
+        check("ls; foo", &[(TokenKind::CommandWord, "ls")]);
+        check("ls) foo", &[(TokenKind::CommandWord, "ls")]);
+        check("", &[]);
+    }
+}
@@ -0,0 +1,186 @@
+use std::fmt;
+
+use text_size::{TextRange, TextSize};
+
+use crate::{unescape, EscapeErrorKind, TokenKind};
+
+/// A problem with a token, found while lexing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LexError {
+    pub kind: LexErrorKind,
+    /// The range of the problem in the input, which may be only part of the token.
+    pub range: TextRange,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LexErrorKind {
+    /// A string without its closing quote, which runs to the end of the line or the input.
+    UnterminatedString,
+    /// A malformed escape in a string, like `\q`.
+    InvalidEscape(EscapeErrorKind),
+    /// A character that cannot start any token, like `&`.
+    InvalidChar(char),
+    /// Letters or underscores straight after a number, like the `px` in `12px`.
+    InvalidNumberSuffix,
+}
+
+impl LexErrorKind {
+    /// A code for the kind of error that stays the same between versions of psh, like the codes
+    /// of parse errors.
+    #[must_use]
+    pub fn code(self) -> &'static str {
+        match self {
+            LexErrorKind::UnterminatedString => "L0001",
+            LexErrorKind::InvalidEscape(_) => "L0002",
+            LexErrorKind::InvalidChar(_) => "L0003",
+            LexErrorKind::InvalidNumberSuffix => "L0004",
+        }
+    }
+}
+
+/// Finds the problems with a token lexed by logos or by hand, except for a number with a suffix,
+/// whose problem is found while lexing the number.
+pub(crate) fn token_errors(kind: TokenKind, text: &str, range: TextRange) -> Vec<LexError> {
+    let start = range.start();
+    match kind {
+        TokenKind::String => escape_errors(&text[1..text.len() - 1], start + TextSize::from(1)),
+        TokenKind::StringStart => escape_errors(&text[1..], start + TextSize::from(1)),
+        TokenKind::StringFragment => escape_errors(text, start),
+        TokenKind::StringEnd => escape_errors(&text[..text.len() - 1], start),
+        TokenKind::MultilineString => {
+            escape_errors(&text[3..text.len() - 3], start + TextSize::from(3))
+        }
+        // Logos only fails on a string when it has no closing quote.
+        TokenKind::Error if text.starts_with(['"', '\'']) => unterminated_string(range),
+        TokenKind::Error => vec![LexError {
+            kind: LexErrorKind::InvalidChar(text.chars().next().unwrap()),
+            range,
+        }],
+        _ => Vec::new(),
+    }
+}
+
+pub(crate) fn unterminated_string(range: TextRange) -> Vec<LexError> {
+    vec![LexError {
+        kind: LexErrorKind::UnterminatedString,
+        range,
+    }]
+}
+
+fn escape_errors(text: &str, offset: TextSize) -> Vec<LexError> {
+    unescape(text)
+        .1
+        .into_iter()
+        .map(|error| LexError {
+            kind: LexErrorKind::InvalidEscape(error.kind),
+            range: error.range + offset,
+        })
+        .collect()
+}
+
+impl fmt::Display for LexErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnterminatedString => f.write_str("unterminated string"),
+            Self::InvalidEscape(kind) => write!(f, "{kind}"),
+            Self::InvalidChar(c) => write!(f, "unexpected character ‘{c}’"),
+            Self::InvalidNumberSuffix => {
+                f.write_str("a number cannot be followed by a letter or ‘_’")
+            }
+        }
+    }
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "error in range {}..{}: {}",
+            u32::from(self.range.start()),
+            u32::from(self.range.end()),
+            self.kind,
+        )
+    }
+}
+
+impl std::error::Error for LexError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Lexer;
+
+    #[track_caller]
+    fn check(input: &str, kind: LexErrorKind, range: std::ops::Range<u32>) {
+        let errors: Vec<_> = Lexer::new(input).flat_map(|token| token.errors).collect();
+        let error = LexError {
+            kind,
+            range: TextRange::new(range.start.into(), range.end.into()),
+        };
+
+        assert_eq!(errors, [error]);
+    }
+
+    #[test]
+    fn unterminated_strings() {
+        check("x \"abc", LexErrorKind::UnterminatedString, 2..6);
+        check("'abc\n", LexErrorKind::UnterminatedString, 0..5);
+        check("r#\"abc\"", LexErrorKind::UnterminatedString, 0..7);
+        check("\"\"\"abc", LexErrorKind::UnterminatedString, 0..6);
+        check("\"a ${b} c", LexErrorKind::UnterminatedString, 7..9);
+        check("$ echo \"abc", LexErrorKind::UnterminatedString, 7..11);
+    }
+
+    #[test]
+    fn invalid_escapes() {
+        check(
+            r#"1 + "a\qb""#,
+            LexErrorKind::InvalidEscape(EscapeErrorKind::UnknownEscape('q')),
+            6..8,
+        );
+        check(
+            r#""${a}\u{}""#,
+            LexErrorKind::InvalidEscape(EscapeErrorKind::EmptyUnicode),
+            5..9,
+        );
+        check(
+            "\"\"\"\n  \\z\n\"\"\"",
+            LexErrorKind::InvalidEscape(EscapeErrorKind::UnknownEscape('z')),
+            6..8,
+        );
+        check(
+            r#"$ echo '\q'"#,
+            LexErrorKind::InvalidEscape(EscapeErrorKind::UnknownEscape('q')),
+            8..10,
+        );
+    }
+
+    #[test]
+    fn raw_strings_have_no_escapes() {
+        assert!(Lexer::new(r#"r"\q""#).all(|token| token.errors.is_empty()));
+    }
+
+    #[test]
+    fn invalid_chars() {
+        check("a & b", LexErrorKind::InvalidChar('&'), 2..3);
+        check("é", LexErrorKind::InvalidChar('é'), 0..2);
+    }
+
+    #[test]
+    fn invalid_number_suffixes() {
+        check("12px", LexErrorKind::InvalidNumberSuffix, 2..4);
+        check("1.5e3 + 1", LexErrorKind::InvalidNumberSuffix, 3..5);
+        check("1_000", LexErrorKind::InvalidNumberSuffix, 1..5);
+    }
+
+    #[test]
+    fn display_lex_error() {
+        let error = Lexer::new("1 & 2").find_map(|token| token.errors.first().copied());
+
+        assert_eq!(
+            error.unwrap().to_string(),
+            "error in range 2..3: unexpected character ‘&’"
+        );
+    }
+}
@@ -4,12 +4,32 @@ use std::ops::Range as StdRange;
 use logos::Logos;
 use text_size::{TextRange, TextSize};
 
+pub use lex_error::{LexError, LexErrorKind};
 pub use token_kind::TokenKind;
+pub use unescape::{unescape, EscapeError, EscapeErrorKind};
 
+mod command;
+mod lex_error;
+mod string;
 mod token_kind;
+mod unescape;
 
 pub struct Lexer<'a> {
     inner: logos::Lexer<'a, TokenKind>,
+    at_start: bool,
+    /// The modes we are nested in, innermost last. Empty for normal lexing at the top level.
+    modes: Vec<Mode>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Mode {
+    /// After a `$`, until the end of the command.
+    Command { after_pipe: bool },
+    /// Inside an interpolated string, between its interpolations.
+    String,
+    /// Inside the `${…}` of an interpolated string, lexing normally. `depth` counts the braces
+    /// opened inside the interpolation, so that we know which `}` closes it.
+    Interpolation { depth: u32 },
 }
 
 impl<'a> Lexer<'a> {
@@ -17,7 +37,174 @@ impl<'a> Lexer<'a> {
     pub fn new(input: &'a str) -> Self {
         Self {
             inner: TokenKind::lexer(input),
+            at_start: true,
+            modes: Vec::new(),
+        }
+    }
+
+    /// Lexes a shebang line, which is only valid as the very first thing in the input.
+    fn shebang(&mut self) -> Option<Token<'a>> {
+        let remainder = self.inner.remainder();
+        if !remainder.starts_with("#!") {
+            return None;
         }
+
+        let len = remainder.find('\n').unwrap_or(remainder.len());
+        Some(self.bump_token(TokenKind::Shebang, len))
+    }
+
+    fn command_token(&mut self, after_pipe: bool) -> Option<Token<'a>> {
+        // A command inside an interpolation ends at the `}` that closes the interpolation.
+        let in_interpolation = matches!(
+            self.modes.iter().rev().nth(1),
+            Some(Mode::Interpolation { .. })
+        );
+
+        let token =
+            command::lex_command_token(self.inner.remainder(), after_pipe, in_interpolation);
+        match token {
+            Some((kind, len)) => {
+                let after_pipe =
+                    kind == TokenKind::Pipe || (after_pipe && kind == TokenKind::Whitespace);
+                self.modes.pop();
+                self.modes.push(Mode::Command { after_pipe });
+
+                Some(self.bump_token(kind, len))
+            }
+            None => {
+                self.modes.pop();
+                None
+            }
+        }
+    }
+
+    /// Lexes the next part of an interpolated string, after one of its interpolations.
+    fn string_token(&mut self) -> Option<Token<'a>> {
+        let remainder = self.inner.remainder();
+        if remainder.is_empty() {
+            return None;
+        }
+
+        let (kind, len) = string::lex_string_part(remainder, false);
+        match kind {
+            TokenKind::InterpStart => self.modes.push(Mode::Interpolation { depth: 0 }),
+            TokenKind::StringEnd | TokenKind::Error => {
+                self.modes.pop();
+            }
+            _ => {}
+        }
+
+        Some(self.bump_token(kind, len))
+    }
+
+    /// Keeps track of braces inside an interpolation, turning the `}` that closes it into an
+    /// `InterpEnd`.
+    fn track_braces(&mut self, kind: TokenKind) -> TokenKind {
+        let Some(Mode::Interpolation { depth }) = self.modes.last_mut() else {
+            return kind;
+        };
+
+        match kind {
+            TokenKind::LBrace => *depth += 1,
+            TokenKind::RBrace if *depth > 0 => *depth -= 1,
+            TokenKind::RBrace => {
+                self.modes.pop();
+                return TokenKind::InterpEnd;
+            }
+            _ => {}
+        }
+
+        kind
+    }
+
+    /// Makes a token from the next `len` bytes, for tokens lexed by hand rather than by logos.
+    fn bump_token(&mut self, kind: TokenKind, len: usize) -> Token<'a> {
+        let start = self.inner.span().end;
+        let end = start + len;
+        self.inner.bump(len);
+
+        let mut token = Token::new(
+            kind,
+            &self.inner.source()[start..end],
+            text_range(start..end),
+        );
+        // The only tokens that fail to lex by hand are strings without a closing quote.
+        if kind == TokenKind::Error {
+            token.errors = lex_error::unterminated_string(token.range);
+        }
+        token
+    }
+
+    /// Turns a number straight followed by letters, like `12px`, into a single `Error` token.
+    fn number_suffix(&mut self, number: Token<'a>) -> Token<'a> {
+        let suffix_len = self
+            .inner
+            .remainder()
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(self.inner.remainder().len());
+        if suffix_len == 0 {
+            return number;
+        }
+
+        let start = number.range.start();
+        let suffix_start = number.range.end();
+        self.inner.bump(suffix_len);
+        let end = text_range(self.inner.span()).end();
+
+        Token {
+            kind: TokenKind::Error,
+            text: &self.inner.source()[TextRange::new(start, end)],
+            range: TextRange::new(start, end),
+            errors: vec![LexError {
+                kind: LexErrorKind::InvalidNumberSuffix,
+                range: TextRange::new(suffix_start, end),
+            }],
+        }
+    }
+
+    fn next_token(&mut self) -> Option<Token<'a>> {
+        if self.at_start {
+            self.at_start = false;
+            if let Some(token) = self.shebang() {
+                return Some(token);
+            }
+        }
+
+        match self.modes.last() {
+            Some(Mode::Command { after_pipe }) => {
+                if let Some(token) = self.command_token(*after_pipe) {
+                    return Some(token);
+                }
+            }
+            Some(Mode::String) => return self.string_token(),
+            Some(Mode::Interpolation { .. }) | None => {}
+        }
+
+        let remainder = self.inner.remainder();
+        if let Some((kind, len)) =
+            string::lex_raw_string(remainder).or_else(|| string::lex_multiline_string(remainder))
+        {
+            return Some(self.bump_token(kind, len));
+        }
+
+        if string::is_interpolated(remainder) {
+            let (kind, len) = string::lex_string_part(remainder, true);
+            self.modes.push(Mode::String);
+            return Some(self.bump_token(kind, len));
+        }
+
+        let kind = self.inner.next()?.unwrap_or(TokenKind::Error);
+        let kind = self.track_braces(kind);
+        if kind == TokenKind::Dollar {
+            self.modes.push(Mode::Command { after_pipe: false });
+        }
+
+        let token = Token::new(kind, self.inner.slice(), text_range(self.inner.span()));
+        if matches!(kind, TokenKind::Integer | TokenKind::Fraction) {
+            return Some(self.number_suffix(token));
+        }
+
+        Some(token)
     }
 }
 
@@ -26,23 +213,33 @@ pub struct Token<'a> {
     pub kind: TokenKind,
     pub text: &'a str,
     pub range: TextRange,
+    /// The problems with the token, like a bad escape in a string. Every `Error` token has at
+    /// least one.
+    pub errors: Vec<LexError>,
+}
+
+impl<'a> Token<'a> {
+    fn new(kind: TokenKind, text: &'a str, range: TextRange) -> Self {
+        Self {
+            kind,
+            text,
+            range,
+            errors: lex_error::token_errors(kind, text, range),
+        }
+    }
 }
 
 impl<'a> Iterator for Lexer<'a> {
     type Item = Token<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let kind = self.inner.next()?.unwrap_or_else(|()| TokenKind::Error);
-        let text = self.inner.slice();
-
-        let range = {
-            let StdRange { start, end } = self.inner.span();
-            let start = TextSize::try_from(start).unwrap();
-            let end = TextSize::try_from(end).unwrap();
+        self.next_token()
+    }
+}
 
-            TextRange::new(start, end)
-        };
+fn text_range(StdRange { start, end }: StdRange<usize>) -> TextRange {
+    let start = TextSize::try_from(start).unwrap();
+    let end = TextSize::try_from(end).unwrap();
 
-        Some(Self::Item { kind, text, range })
-    }
+    TextRange::new(start, end)
 }
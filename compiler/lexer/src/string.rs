@@ -0,0 +1,160 @@
+use crate::TokenKind;
+
+/// Lexes a raw string like `r"C:\path"` or `r#"say "hi""#`, if there is one at the start of
+/// `remainder`.
+///
+/// A raw string has no escapes, and ends at the first `"` followed by as many `#`s as it
+/// started with. A raw string without an end swallows the rest of the input.
+pub(crate) fn lex_raw_string(remainder: &str) -> Option<(TokenKind, usize)> {
+    let after_r = remainder.strip_prefix('r')?;
+    let hashes = after_r.len() - after_r.trim_start_matches('#').len();
+    let contents = after_r[hashes..].strip_prefix('"')?;
+
+    let closing = format!("\"{}", "#".repeat(hashes));
+    let token = match contents.find(&closing) {
+        Some(idx) => (
+            TokenKind::RawString,
+            remainder.len() - contents.len() + idx + closing.len(),
+        ),
+        None => (TokenKind::Error, remainder.len()),
+    };
+
+    Some(token)
+}
+
+/// Lexes a multi-line string between `"""`s, if there is one at the start of `remainder`.
+///
+/// A multi-line string without an end swallows the rest of the input.
+pub(crate) fn lex_multiline_string(remainder: &str) -> Option<(TokenKind, usize)> {
+    let contents = remainder.strip_prefix(r#"""""#)?;
+
+    let mut escaped = false;
+    for (idx, c) in contents.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' if contents[idx..].starts_with(r#"""""#) => {
+                return Some((TokenKind::MultilineString, 3 + idx + 3));
+            }
+            _ => {}
+        }
+    }
+
+    Some((TokenKind::Error, remainder.len()))
+}
+
+/// Whether the string at the start of `remainder` contains an interpolation like `${name}`.
+///
+/// Strings without one are lexed by logos as a single `String` token.
+pub(crate) fn is_interpolated(remainder: &str) -> bool {
+    remainder.starts_with('"')
+        && matches!(
+            lex_string_part(remainder, true),
+            (TokenKind::StringStart, _)
+        )
+}
+
+/// Lexes the next part of an interpolated string: its text up to the next `${` or closing quote,
+/// or the `${` starting an interpolation.
+///
+/// `at_start` is set for the opening quote of the string, and unset when continuing the string
+/// after an interpolation. A string without a closing quote swallows the rest of the input.
+pub(crate) fn lex_string_part(remainder: &str, at_start: bool) -> (TokenKind, usize) {
+    if !at_start && remainder.starts_with("${") {
+        return (TokenKind::InterpStart, 2);
+    }
+
+    let mut escaped = false;
+    let mut chars = remainder
+        .char_indices()
+        .skip(usize::from(at_start))
+        .peekable();
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return (TokenKind::StringEnd, idx + 1),
+            '$' if chars.peek().is_some_and(|(_, next)| *next == '{') => {
+                let kind = if at_start {
+                    TokenKind::StringStart
+                } else {
+                    TokenKind::StringFragment
+                };
+                return (kind, idx);
+            }
+            _ => {}
+        }
+    }
+
+    (TokenKind::Error, remainder.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lex_raw_strings() {
+        assert_eq!(
+            lex_raw_string(r#"r"C:\path" x"#),
+            Some((TokenKind::RawString, 10))
+        );
+        assert_eq!(
+            lex_raw_string(r###"r##"a "# b"## x"###),
+            Some((TokenKind::RawString, 13))
+        );
+        assert_eq!(lex_raw_string(r##"r#"a" b"##), Some((TokenKind::Error, 7)));
+        assert_eq!(lex_raw_string("rust"), None);
+        assert_eq!(lex_raw_string("r#x"), None);
+    }
+
+    #[test]
+    fn lex_multiline_strings() {
+        assert_eq!(
+            lex_multiline_string("\"\"\"\n  a\n  \"\"\" x"),
+            Some((TokenKind::MultilineString, 13))
+        );
+        assert_eq!(
+            lex_multiline_string(r#""""a \""" b""" x"#),
+            Some((TokenKind::MultilineString, 14))
+        );
+        assert_eq!(lex_multiline_string(r#""""a"#), Some((TokenKind::Error, 4)));
+        assert_eq!(lex_multiline_string(r#""a""#), None);
+    }
+
+    #[test]
+    fn plain_strings_are_not_interpolated() {
+        assert!(!is_interpolated(r#""hello""#));
+        assert!(!is_interpolated(r#""cost: $5""#));
+        assert!(!is_interpolated(r#""escaped \${name}""#));
+        assert!(!is_interpolated("'${name}'"));
+    }
+
+    #[test]
+    fn lex_start_of_interpolated_string() {
+        assert!(is_interpolated(r#""hello ${name}""#));
+        assert_eq!(
+            lex_string_part(r#""hello ${name}""#, true),
+            (TokenKind::StringStart, 7)
+        );
+        assert_eq!(
+            lex_string_part(r#""${name}""#, true),
+            (TokenKind::StringStart, 1)
+        );
+    }
+
+    #[test]
+    fn lex_rest_of_interpolated_string() {
+        assert_eq!(
+            lex_string_part("${b}\"", false),
+            (TokenKind::InterpStart, 2)
+        );
+        assert_eq!(
+            lex_string_part(" and ${b}\"", false),
+            (TokenKind::StringFragment, 5)
+        );
+        assert_eq!(lex_string_part("!\"", false), (TokenKind::StringEnd, 2));
+        assert_eq!(lex_string_part("\"", false), (TokenKind::StringEnd, 1));
+        assert_eq!(lex_string_part("abc", false), (TokenKind::Error, 3));
+    }
+}
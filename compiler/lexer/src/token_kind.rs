@@ -19,6 +19,16 @@ pub enum TokenKind {
     ThenKw,
     #[token("else")]
     ElseKw,
+    #[token("true")]
+    TrueKw,
+    #[token("false")]
+    FalseKw,
+    #[token("and")]
+    AndKw,
+    #[token("or")]
+    OrKw,
+    #[token("not")]
+    NotKw,
 
     #[regex("_?(?&alpha_num_id)(_(?&alpha_num_id))+")]
     #[regex("_?(?&alpha_num_id)")]
@@ -30,24 +40,58 @@ pub enum TokenKind {
     #[regex(r"[0-9]+\.[0-9]+")]
     Fraction,
 
-    #[regex(r#""([^"\\]|\\t|\\u|\\n|\\")*""#)]
-    #[regex(r#"'([^'\\]|\\t|\\u|\\n|\\")*'"#)]
+    // Any character can follow a `\` here, and malformed escapes are reported by `unescape`,
+    // so that one bad escape does not turn the whole string into an error.
+    #[regex(r#""([^"\\]|\\(.|\n))*""#)]
+    #[regex(r#"'([^'\\]|\\(.|\n))*'"#)]
     String,
 
+    /// A string without escapes, like `r"C:\path"` or `r#"say "hi""#`.
+    RawString,
+
+    /// A string between `"""`s that can span several lines.
+    MultilineString,
+
+    /// The opening quote and leading text of a string containing interpolations, like
+    /// `"hello ` in `"hello ${name}!"`.
+    StringStart,
+
+    /// Text between two interpolations of a string.
+    StringFragment,
+
+    /// The trailing text and closing quote of a string containing interpolations, like `!"` in
+    /// `"hello ${name}!"`.
+    StringEnd,
+
+    /// The `${` opening an interpolation in a string.
+    InterpStart,
+
+    /// The `}` closing an interpolation in a string.
+    InterpEnd,
+
     #[token(":")]
     Colon,
     #[token("::")]
     DoubleColon,
 
+    #[token(".")]
+    Dot,
+
     #[token(",")]
     Comma,
 
+    #[token(";")]
+    Semicolon,
+
     #[token("+")]
     Plus,
 
     #[token("-")]
     Minus,
 
+    #[token("->")]
+    Arrow,
+
     #[token("*")]
     Star,
 
@@ -57,6 +101,12 @@ pub enum TokenKind {
     #[token("=")]
     Equals,
 
+    #[token("==")]
+    DoubleEquals,
+
+    #[token("!=")]
+    BangEquals,
+
     #[token("{")]
     LBrace,
 
@@ -81,19 +131,44 @@ pub enum TokenKind {
     #[token(">")]
     RAngle,
 
+    #[token("<=")]
+    LAngleEquals,
+
+    #[token(">=")]
+    RAngleEquals,
+
     #[token("|")]
     Pipe,
 
+    #[token("$")]
+    Dollar,
+
+    /// A word in a command, like `--force` in `$ git push --force`.
+    CommandWord,
+
+    /// A redirection of a command's stdout that appends to a file, only lexed in commands.
+    DoubleRAngle,
+
+    /// A redirection of a command's stderr to a file, only lexed in commands.
+    StderrRAngle,
+
+    /// A redirection of a command's stderr to wherever its stdout goes, only lexed in commands.
+    StderrToStdout,
+
     #[regex("--[^\n]*")]
     Comment,
 
+    /// An interpreter directive like `#!/usr/bin/env psh`, only recognized at the very start
+    /// of the input.
+    Shebang,
+
     Error,
 }
 
 impl TokenKind {
     #[must_use]
     pub fn is_trivia(self) -> bool {
-        matches!(self, Self::Whitespace | Self::Comment)
+        matches!(self, Self::Whitespace | Self::Comment | Self::Shebang)
     }
 }
 
@@ -105,18 +180,35 @@ impl fmt::Display for TokenKind {
             Self::IfKw => "‘if‘",
             Self::ThenKw => "‘then‘",
             Self::ElseKw => "‘else‘",
+            Self::TrueKw => "‘true’",
+            Self::FalseKw => "‘false’",
+            Self::AndKw => "‘and’",
+            Self::OrKw => "‘or’",
+            Self::NotKw => "‘not’",
             Self::Ident => "identifier",
             Self::Integer => "integer",
             Self::Fraction => "fraction",
             Self::String => "string",
+            Self::RawString => "raw string",
+            Self::MultilineString => "multi-line string",
+            Self::StringStart => "the start of a string",
+            Self::StringFragment => "part of a string",
+            Self::StringEnd => "the end of a string",
+            Self::InterpStart => "‘${’",
+            Self::InterpEnd => "‘}’",
             Self::Colon => "‘:’",
             Self::DoubleColon => "‘::’",
+            Self::Dot => "‘.’",
             Self::Comma => "‘,’",
+            Self::Semicolon => "‘;’",
             Self::Plus => "‘+’",
             Self::Minus => "‘-’",
+            Self::Arrow => "‘->’",
             Self::Star => "‘*’",
             Self::Slash => "‘/’",
             Self::Equals => "‘=’",
+            Self::DoubleEquals => "‘==’",
+            Self::BangEquals => "‘!=’",
             Self::LParen => "‘(’",
             Self::RParen => "‘)’",
             Self::LBrace => "‘{’",
@@ -125,8 +217,16 @@ impl fmt::Display for TokenKind {
             Self::RBracket => "‘]’",
             Self::LAngle => "‘<’",
             Self::RAngle => "‘>’",
+            Self::LAngleEquals => "‘<=’",
+            Self::RAngleEquals => "‘>=’",
             Self::Pipe => "‘|’",
+            Self::Dollar => "‘$’",
+            Self::CommandWord => "word",
+            Self::DoubleRAngle => "‘>>’",
+            Self::StderrRAngle => "‘2>’",
+            Self::StderrToStdout => "‘2>&1’",
             Self::Comment => "comment",
+            Self::Shebang => "shebang",
             Self::Error => "an unrecognized token",
         })
     }
@@ -182,6 +282,11 @@ mod tests {
             "if" => TokenKind::IfKw,
             "then" => TokenKind::ThenKw,
             "else" => TokenKind::ElseKw,
+            "true" => TokenKind::TrueKw,
+            "false" => TokenKind::FalseKw,
+            "and" => TokenKind::AndKw,
+            "or" => TokenKind::OrKw,
+            "not" => TokenKind::NotKw,
         };
 
         for (source, expected) in source {
@@ -194,17 +299,27 @@ mod tests {
         let source = btreemap! {
             ":" => TokenKind::Colon,
             "::" => TokenKind::DoubleColon,
+            "." => TokenKind::Dot,
             "," => TokenKind::Comma,
+            ";" => TokenKind::Semicolon,
             "+" => TokenKind::Plus,
             "-" => TokenKind::Minus,
+            "->" => TokenKind::Arrow,
             "*" => TokenKind::Star,
             "/" => TokenKind::Slash,
             "=" => TokenKind::Equals,
+            "==" => TokenKind::DoubleEquals,
+            "!=" => TokenKind::BangEquals,
+            "<" => TokenKind::LAngle,
+            ">" => TokenKind::RAngle,
+            "<=" => TokenKind::LAngleEquals,
+            ">=" => TokenKind::RAngleEquals,
             "(" => TokenKind::LParen,
             ")" => TokenKind::RParen,
             "{" => TokenKind::LBrace,
             "}" => TokenKind::RBrace,
             "|" => TokenKind::Pipe,
+            "$" => TokenKind::Dollar,
         };
 
         for (source, expected) in source {
@@ -217,6 +332,13 @@ mod tests {
         let source: BTreeMap<&str, Vec<TokenKind>> = btreemap! {
             "||" => vec![TokenKind::Pipe, TokenKind::Pipe],
             "<::>" => vec![TokenKind::LAngle, TokenKind::DoubleColon, TokenKind::RAngle],
+            "a.b" => vec![TokenKind::Ident, TokenKind::Dot, TokenKind::Ident],
+            "1.a" => vec![TokenKind::Integer, TokenKind::Dot, TokenKind::Ident],
+            "->-" => vec![TokenKind::Arrow, TokenKind::Minus],
+            "-->" => vec![TokenKind::Comment],
+            "a<=b" => vec![TokenKind::Ident, TokenKind::LAngleEquals, TokenKind::Ident],
+            "===" => vec![TokenKind::DoubleEquals, TokenKind::Equals],
+            "android" => vec![TokenKind::Ident],
         };
 
         for (source, expected) in source {
@@ -261,8 +383,175 @@ mod tests {
         check("'char'", TokenKind::String);
     }
 
+    #[test]
+    fn lex_string_with_escapes() {
+        check(r#""\\""#, TokenKind::String);
+        check(r#""\r\0""#, TokenKind::String);
+        check(r"'\''", TokenKind::String);
+        check(r#""\u{1F600}""#, TokenKind::String);
+        check(r#""\q""#, TokenKind::String);
+    }
+
+    #[test]
+    fn lex_raw_string() {
+        check(r#"r"C:\path""#, TokenKind::RawString);
+        check(r##"r#"say "hi""#"##, TokenKind::RawString);
+        check(r###"r##"a "# b"##"###, TokenKind::RawString);
+        check_multiple(
+            "r x",
+            &[TokenKind::Ident, TokenKind::Whitespace, TokenKind::Ident],
+        );
+    }
+
+    #[test]
+    fn lex_multiline_string() {
+        check(
+            "\"\"\"\n  one\n  \"two\"\n  \"\"\"",
+            TokenKind::MultilineString,
+        );
+        check(r#""""""""#, TokenKind::MultilineString);
+        check_multiple(
+            r#""""a""" 1"#,
+            &[
+                TokenKind::MultilineString,
+                TokenKind::Whitespace,
+                TokenKind::Integer,
+            ],
+        );
+    }
+
     #[test]
     fn lex_comment() {
         check("-- foo", TokenKind::Comment);
     }
+
+    #[test]
+    fn lex_shebang() {
+        check("#!/usr/bin/env psh", TokenKind::Shebang);
+        check_multiple(
+            "#!/usr/bin/env psh\n1",
+            &[
+                TokenKind::Shebang,
+                TokenKind::Whitespace,
+                TokenKind::Integer,
+            ],
+        );
+    }
+
+    #[test]
+    fn lex_shebang_only_at_start() {
+        check_multiple(
+            " #!psh",
+            &[
+                TokenKind::Whitespace,
+                TokenKind::Error,
+                TokenKind::Error,
+                TokenKind::Ident,
+            ],
+        );
+    }
+
+    #[test]
+    fn lex_command() {
+        check_multiple(
+            "$ ls -la | wc",
+            &[
+                TokenKind::Dollar,
+                TokenKind::Whitespace,
+                TokenKind::CommandWord,
+                TokenKind::Whitespace,
+                TokenKind::CommandWord,
+                TokenKind::Whitespace,
+                TokenKind::Pipe,
+                TokenKind::Whitespace,
+                TokenKind::CommandWord,
+            ],
+        );
+    }
+
+    #[test]
+    fn lex_interpolated_string() {
+        check_multiple(
+            r#""a ${b + "c"} d ${{x: 1}.x}!""#,
+            &[
+                TokenKind::StringStart,
+                TokenKind::InterpStart,
+                TokenKind::Ident,
+                TokenKind::Whitespace,
+                TokenKind::Plus,
+                TokenKind::Whitespace,
+                TokenKind::String,
+                TokenKind::InterpEnd,
+                TokenKind::StringFragment,
+                TokenKind::InterpStart,
+                TokenKind::LBrace,
+                TokenKind::Ident,
+                TokenKind::Colon,
+                TokenKind::Whitespace,
+                TokenKind::Integer,
+                TokenKind::RBrace,
+                TokenKind::Dot,
+                TokenKind::Ident,
+                TokenKind::InterpEnd,
+                TokenKind::StringEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn lex_nested_interpolated_strings() {
+        check_multiple(
+            r#""${"${a}"}" 1"#,
+            &[
+                TokenKind::StringStart,
+                TokenKind::InterpStart,
+                TokenKind::StringStart,
+                TokenKind::InterpStart,
+                TokenKind::Ident,
+                TokenKind::InterpEnd,
+                TokenKind::StringEnd,
+                TokenKind::InterpEnd,
+                TokenKind::StringEnd,
+                TokenKind::Whitespace,
+                TokenKind::Integer,
+            ],
+        );
+    }
+
+    #[test]
+    fn lex_command_in_interpolation() {
+        check_multiple(
+            r#""${$ whoami}""#,
+            &[
+                TokenKind::StringStart,
+                TokenKind::InterpStart,
+                TokenKind::Dollar,
+                TokenKind::Whitespace,
+                TokenKind::CommandWord,
+                TokenKind::InterpEnd,
+                TokenKind::StringEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn lex_after_command() {
+        check_multiple(
+            "($ ls --all) -- done\n-1",
+            &[
+                TokenKind::LParen,
+                TokenKind::Dollar,
+                TokenKind::Whitespace,
+                TokenKind::CommandWord,
+                TokenKind::Whitespace,
+                TokenKind::CommandWord,
+                TokenKind::RParen,
+                TokenKind::Whitespace,
+                TokenKind::Comment,
+                TokenKind::Whitespace,
+                TokenKind::Minus,
+                TokenKind::Integer,
+            ],
+        );
+    }
 }
@@ -0,0 +1,263 @@
+use std::fmt;
+
+use text_size::{TextRange, TextSize};
+
+/// A malformed escape in the text of a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EscapeError {
+    pub kind: EscapeErrorKind,
+    /// The range of the whole escape, relative to the start of the text given to [`unescape`].
+    pub range: TextRange,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EscapeErrorKind {
+    /// A `\` followed by a character with no escape, like `\q`.
+    UnknownEscape(char),
+    /// A `\` at the very end of the text.
+    LoneBackslash,
+    /// A `\u` without the `{` of `\u{…}`.
+    MissingUnicodeBrace,
+    /// A `\u{` without its closing `}`.
+    UnclosedUnicode,
+    /// A `\u{}` with no digits.
+    EmptyUnicode,
+    /// A character in a `\u{…}` that is not a hex digit.
+    InvalidUnicodeDigit(char),
+    /// A `\u{…}` with more than six digits.
+    OverlongUnicode,
+    /// A `\u{…}` that is not a Unicode scalar value, like a surrogate or anything past
+    /// `10FFFF`.
+    InvalidCodePoint(u32),
+}
+
+/// Resolves the escapes in the text of a string, without its quotes.
+///
+/// The escapes are `\n`, `\r`, `\t`, `\0`, `\\`, `\"`, `\'`, `\$` and `\u{…}` with up to six
+/// hex digits. A malformed escape is reported as an error and kept in the value as it was
+/// written.
+#[must_use]
+pub fn unescape(text: &str) -> (String, Vec<EscapeError>) {
+    let mut value = String::with_capacity(text.len());
+    let mut errors = Vec::new();
+
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+
+        let escaped = match chars.next() {
+            None => Err(EscapeErrorKind::LoneBackslash),
+            Some((_, 'n')) => Ok('\n'),
+            Some((_, 'r')) => Ok('\r'),
+            Some((_, 't')) => Ok('\t'),
+            Some((_, '0')) => Ok('\0'),
+            Some((_, '\\')) => Ok('\\'),
+            Some((_, '"')) => Ok('"'),
+            Some((_, '\'')) => Ok('\''),
+            Some((_, '$')) => Ok('$'),
+            Some((_, 'u')) => unicode_escape(&mut chars),
+            Some((_, other)) => Err(EscapeErrorKind::UnknownEscape(other)),
+        };
+
+        let end = chars.peek().map_or(text.len(), |(idx, _)| *idx);
+        match escaped {
+            Ok(c) => value.push(c),
+            Err(kind) => {
+                value.push_str(&text[start..end]);
+                errors.push(EscapeError {
+                    kind,
+                    range: TextRange::new(text_size(start), text_size(end)),
+                });
+            }
+        }
+    }
+
+    (value, errors)
+}
+
+/// Decodes the `{…}` of a `\u{…}` escape.
+///
+/// On an error, as much of the escape as makes sense is consumed: up to the closing `}` if
+/// there is one, or otherwise up to the first character that cannot be part of it.
+fn unicode_escape(
+    chars: &mut std::iter::Peekable<impl Iterator<Item = (usize, char)>>,
+) -> Result<char, EscapeErrorKind> {
+    if chars.next_if(|(_, c)| *c == '{').is_none() {
+        return Err(EscapeErrorKind::MissingUnicodeBrace);
+    }
+
+    let mut digits = String::new();
+    let mut error = None;
+    loop {
+        match chars.peek() {
+            Some((_, '}')) => {
+                chars.next();
+                break;
+            }
+            Some((_, c)) if c.is_ascii_hexdigit() => digits.push(*c),
+            Some((_, c)) if c.is_alphanumeric() => {
+                error.get_or_insert(EscapeErrorKind::InvalidUnicodeDigit(*c));
+            }
+            _ => return Err(EscapeErrorKind::UnclosedUnicode),
+        }
+        chars.next();
+    }
+
+    if let Some(error) = error {
+        return Err(error);
+    }
+    if digits.is_empty() {
+        return Err(EscapeErrorKind::EmptyUnicode);
+    }
+    if digits.len() > 6 {
+        return Err(EscapeErrorKind::OverlongUnicode);
+    }
+
+    let code_point = u32::from_str_radix(&digits, 16).expect("only hex digits were collected");
+    char::from_u32(code_point).ok_or(EscapeErrorKind::InvalidCodePoint(code_point))
+}
+
+fn text_size(offset: usize) -> TextSize {
+    TextSize::try_from(offset).unwrap()
+}
+
+impl fmt::Display for EscapeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownEscape(c) => write!(f, "unknown escape ‘\\{c}’"),
+            Self::LoneBackslash => f.write_str("a ‘\\’ must be followed by an escape"),
+            Self::MissingUnicodeBrace => f.write_str("expected ‘{’ after ‘\\u’"),
+            Self::UnclosedUnicode => f.write_str("expected ‘}’ at the end of a unicode escape"),
+            Self::EmptyUnicode => f.write_str("a unicode escape needs at least one hex digit"),
+            Self::InvalidUnicodeDigit(c) => {
+                write!(f, "‘{c}’ is not a hex digit in a unicode escape")
+            }
+            Self::OverlongUnicode => f.write_str("a unicode escape has at most six hex digits"),
+            Self::InvalidCodePoint(code_point) => {
+                write!(f, "‘{code_point:X}’ is not a unicode character")
+            }
+        }
+    }
+}
+
+impl fmt::Display for EscapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "error in range {}..{}: {}",
+            u32::from(self.range.start()),
+            u32::from(self.range.end()),
+            self.kind,
+        )
+    }
+}
+
+impl std::error::Error for EscapeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    fn check(text: &str, expected: &str) {
+        assert_eq!(unescape(text), (expected.to_string(), vec![]));
+    }
+
+    #[track_caller]
+    fn check_error(text: &str, expected: &str, kind: EscapeErrorKind, range: std::ops::Range<u32>) {
+        let error = EscapeError {
+            kind,
+            range: TextRange::new(range.start.into(), range.end.into()),
+        };
+
+        assert_eq!(unescape(text), (expected.to_string(), vec![error]));
+    }
+
+    #[test]
+    fn unescape_simple_escapes() {
+        check(r"a\nb\rc\td\0", "a\nb\rc\td\0");
+        check(r#"\\ \" \' \$"#, r#"\ " ' $"#);
+        check("no escapes ✓", "no escapes ✓");
+    }
+
+    #[test]
+    fn unescape_unicode() {
+        check(r"\u{1F600}", "😀");
+        check(r"\u{41}\u{00e9}", "Aé");
+        check(r"\u{10FFFF}", "\u{10FFFF}");
+    }
+
+    #[test]
+    fn error_on_unknown_escape() {
+        check_error(r"a\qb", r"a\qb", EscapeErrorKind::UnknownEscape('q'), 1..3);
+        check_error(r"é\é", r"é\é", EscapeErrorKind::UnknownEscape('é'), 2..5);
+    }
+
+    #[test]
+    fn error_on_lone_backslash() {
+        check_error(r"ab\", r"ab\", EscapeErrorKind::LoneBackslash, 2..3);
+    }
+
+    #[test]
+    fn error_on_malformed_unicode() {
+        check_error(r"\u41", r"\u41", EscapeErrorKind::MissingUnicodeBrace, 0..2);
+        check_error(
+            r"\u{41 b",
+            r"\u{41 b",
+            EscapeErrorKind::UnclosedUnicode,
+            0..5,
+        );
+        check_error(r"\u{}", r"\u{}", EscapeErrorKind::EmptyUnicode, 0..4);
+        check_error(
+            r"\u{4g}x",
+            r"\u{4g}x",
+            EscapeErrorKind::InvalidUnicodeDigit('g'),
+            0..6,
+        );
+        check_error(
+            r"\u{1000000}",
+            r"\u{1000000}",
+            EscapeErrorKind::OverlongUnicode,
+            0..11,
+        );
+        check_error(
+            r"\u{D800}",
+            r"\u{D800}",
+            EscapeErrorKind::InvalidCodePoint(0xD800),
+            0..8,
+        );
+        check_error(
+            r"\u{110000}",
+            r"\u{110000}",
+            EscapeErrorKind::InvalidCodePoint(0x11_0000),
+            0..10,
+        );
+    }
+
+    #[test]
+    fn reports_every_malformed_escape() {
+        let (value, errors) = unescape(r"\q ok\n \u{}");
+
+        assert_eq!(value, "\\q ok\n \\u{}");
+        assert_eq!(
+            errors.iter().map(|error| error.range).collect::<Vec<_>>(),
+            [
+                TextRange::new(0.into(), 2.into()),
+                TextRange::new(8.into(), 12.into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn display_escape_error() {
+        let (_, errors) = unescape(r"ab\q");
+
+        assert_eq!(
+            errors[0].to_string(),
+            "error in range 2..4: unknown escape ‘\\q’"
+        );
+    }
+}
@@ -0,0 +1,356 @@
+//! Rendering errors for people, with line and column numbers and the offending source.
+
+use std::fmt::Write as _;
+
+use text_size::{TextRange, TextSize};
+
+use crate::parser::ParseErrorKind;
+use crate::ParseError;
+
+/// A line and column in some text, both counted from zero.
+///
+/// The column is in bytes from the start of the line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineCol {
+    pub line: u32,
+    pub col: u32,
+}
+
+/// Maps offsets in some text to lines and columns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex {
+    /// The offset of the start of each line. The first line always starts at 0.
+    line_starts: Vec<TextSize>,
+    len: TextSize,
+}
+
+impl LineIndex {
+    #[must_use]
+    pub fn new(text: &str) -> Self {
+        let mut line_starts = vec![TextSize::from(0)];
+        line_starts.extend(
+            text.match_indices('\n')
+                .map(|(idx, _)| TextSize::try_from(idx + 1).unwrap()),
+        );
+
+        Self {
+            line_starts,
+            len: TextSize::of(text),
+        }
+    }
+
+    /// The line and column of `offset`. An offset past the end of the text is treated as the end.
+    #[must_use]
+    pub fn line_col(&self, offset: TextSize) -> LineCol {
+        let offset = offset.min(self.len);
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+
+        LineCol {
+            line: u32::try_from(line).unwrap(),
+            col: u32::from(offset - self.line_starts[line]),
+        }
+    }
+
+    /// The range of `line`, without the newline that ends it.
+    #[must_use]
+    pub fn line_range(&self, line: u32) -> Option<TextRange> {
+        let line = usize::try_from(line).ok()?;
+        let start = *self.line_starts.get(line)?;
+        let end = self
+            .line_starts
+            .get(line + 1)
+            .map_or(self.len, |next| *next - TextSize::from(1));
+
+        Some(TextRange::new(start, end))
+    }
+}
+
+/// Renders errors as a message, the position of the error and the line it is on with carets
+/// under the offending source:
+///
+/// ```text
+/// error[P0030]: found ‘)’, but expected integer or identifier
+///  --> script.psh:2:9
+///   |
+/// 2 | let a = )
+///   |         ^ while parsing the expression in a value definition
+/// ```
+#[derive(Debug)]
+pub struct Renderer<'a> {
+    source: &'a str,
+    line_index: LineIndex,
+    path: Option<&'a str>,
+    color: bool,
+}
+
+impl<'a> Renderer<'a> {
+    #[must_use]
+    pub fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            line_index: LineIndex::new(source),
+            path: None,
+            color: false,
+        }
+    }
+
+    /// Names the file the source came from in front of the line and column.
+    #[must_use]
+    pub fn with_path(self, path: &'a str) -> Self {
+        Self {
+            path: Some(path),
+            ..self
+        }
+    }
+
+    /// Colors the output with ANSI escapes, for a terminal.
+    #[must_use]
+    pub fn with_color(self, color: bool) -> Self {
+        Self { color, ..self }
+    }
+
+    #[must_use]
+    pub fn render(&self, error: &ParseError) -> String {
+        let label = match error.kind {
+            ParseErrorKind::Lex(_) => None,
+            _ => Some(format!("while parsing {}", error.context.context_name())),
+        };
+
+        self.render_parts(
+            &format!("error[{}]", error.code()),
+            &error.message(),
+            error.range(),
+            label.as_deref(),
+        )
+    }
+
+    /// Renders an error from outside the parser, like one from evaluating the source, as
+    /// `error: {message}` with carets under `range`.
+    #[must_use]
+    pub fn render_at(&self, message: &str, range: TextRange) -> String {
+        self.render_parts("error", message, range, None)
+    }
+
+    fn render_parts(
+        &self,
+        header: &str,
+        message: &str,
+        range: TextRange,
+        label: Option<&str>,
+    ) -> String {
+        let mut s = String::new();
+
+        let _ = writeln!(s, "{}: {message}", self.paint(ERROR, header));
+
+        let start = self.line_index.line_col(range.start());
+        let line_range = self.line_index.line_range(start.line).unwrap();
+        let line = self.source[line_range].trim_end_matches('\r');
+
+        let (before, rest) = line.split_at((start.col as usize).min(line.len()));
+        let line_number = (start.line + 1).to_string();
+        let gutter = " ".repeat(line_number.len());
+        let col = before.chars().count() + 1;
+
+        let location = match self.path {
+            Some(path) => format!("{path}:{line_number}:{col}"),
+            None => format!("{line_number}:{col}"),
+        };
+        let _ = writeln!(s, "{gutter}{} {location}", self.paint(GUTTER, "-->"));
+        let _ = writeln!(s, "{gutter} {}", self.paint(GUTTER, "|"));
+        let _ = writeln!(
+            s,
+            "{} {line}",
+            self.paint(GUTTER, &format!("{line_number} |"))
+        );
+
+        // Carets stop at the end of the line for a token that spans several, like a string.
+        let underlined = &rest[..usize::from(range.len()).min(rest.len())];
+        let carets = "^".repeat(underlined.chars().count().max(1));
+        // Tabs are kept so that the carets line up however wide the terminal shows them.
+        let indent: String = before
+            .chars()
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let _ = write!(
+            s,
+            "{gutter} {} {indent}{}",
+            self.paint(GUTTER, "|"),
+            self.paint(ERROR, &carets),
+        );
+        if let Some(label) = label {
+            let _ = write!(s, " {label}");
+        }
+
+        s
+    }
+
+    fn paint(&self, style: &str, text: &str) -> String {
+        if self.color {
+            format!("\x1b[{style}m{text}\x1b[0m")
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// Bold red.
+const ERROR: &str = "1;31";
+/// Bold blue.
+const GUTTER: &str = "1;34";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    fn check(input: &str, expected: &str) {
+        let parse = crate::parse_source_file(input);
+        let rendered = parse
+            .errors()
+            .iter()
+            .map(|error| Renderer::new(input).render(error))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        assert_eq!(rendered, expected);
+    }
+
+    #[test]
+    fn line_col_of_offsets() {
+        let index = LineIndex::new("ab\ncd\n\nef");
+
+        let line_col = |offset: u32| {
+            let LineCol { line, col } = index.line_col(offset.into());
+            (line, col)
+        };
+        assert_eq!(line_col(0), (0, 0));
+        assert_eq!(line_col(2), (0, 2));
+        assert_eq!(line_col(3), (1, 0));
+        assert_eq!(line_col(6), (2, 0));
+        assert_eq!(line_col(8), (3, 1));
+        assert_eq!(line_col(100), (3, 2));
+    }
+
+    #[test]
+    fn line_ranges() {
+        let index = LineIndex::new("ab\n\ncd");
+
+        assert_eq!(
+            index.line_range(0),
+            Some(TextRange::new(0.into(), 2.into()))
+        );
+        assert_eq!(
+            index.line_range(1),
+            Some(TextRange::new(3.into(), 3.into()))
+        );
+        assert_eq!(
+            index.line_range(2),
+            Some(TextRange::new(4.into(), 6.into()))
+        );
+        assert_eq!(index.line_range(3), None);
+    }
+
+    #[test]
+    fn render_unexpected_token() {
+        check(
+            "let a = 1\nlet b = a )",
+            "\
+error[P0053]: found ‘)’, but expected ‘;’
+ --> 2:11
+  |
+2 | let b = a )
+  |           ^ while parsing a newline or ‘;’ between statements",
+        );
+    }
+
+    #[test]
+    fn render_missing_token() {
+        check(
+            "let a = 1 +",
+            "\
+error[P0030]: missing expected integer, fraction, string, ‘true’, ‘false’, identifier, ‘-’, ‘not’, ‘(’, ‘[’, ‘{’, ‘|’, ‘if‘ or ‘$’
+ --> 1:12
+  |
+1 | let a = 1 +
+  |            ^ while parsing the expression in a value definition",
+        );
+    }
+
+    #[test]
+    fn render_underlines_whole_token() {
+        check(
+            "[1, then]",
+            "\
+error[P0006]: found ‘then‘, but expected integer, fraction, string, ‘true’, ‘false’, identifier, ‘-’, ‘not’, ‘(’, ‘[’, ‘{’, ‘|’, ‘if‘ or ‘$’
+ --> 1:5
+  |
+1 | [1, then]
+  |     ^^^^ while parsing an element of an array",
+        );
+    }
+
+    #[test]
+    fn render_counts_columns_in_chars() {
+        check(
+            "let s = \"é…\" )",
+            "\
+error[P0053]: found ‘)’, but expected ‘;’
+ --> 1:14
+  |
+1 | let s = \"é…\" )
+  |              ^ while parsing a newline or ‘;’ between statements",
+        );
+    }
+
+    #[test]
+    fn render_lex_error() {
+        check(
+            "let s = \"a\\qb\"",
+            "\
+error[L0002]: unknown escape ‘\\q’
+ --> 1:11
+  |
+1 | let s = \"a\\qb\"
+  |           ^^",
+        );
+    }
+
+    #[test]
+    fn render_error_at_range() {
+        let input = "let a = 1\nlet b = a / 0";
+
+        assert_eq!(
+            Renderer::new(input).render_at(
+                "cannot divide by zero",
+                TextRange::new(18.into(), 23.into())
+            ),
+            "\
+error: cannot divide by zero
+ --> 2:9
+  |
+2 | let b = a / 0
+  |         ^^^^^"
+        );
+    }
+
+    #[test]
+    fn render_with_path_and_color() {
+        let input = "[1 2]";
+        let parse = crate::parse_source_file(input);
+
+        let rendered = Renderer::new(input)
+            .with_path("script.psh")
+            .with_color(true)
+            .render(&parse.errors()[0]);
+
+        assert_eq!(
+            rendered,
+            "\
+\x1b[1;31merror[P0007]\x1b[0m: missing expected ‘,’
+ \x1b[1;34m-->\x1b[0m script.psh:1:4
+  \x1b[1;34m|\x1b[0m
+\x1b[1;34m1 |\x1b[0m [1 2]
+  \x1b[1;34m|\x1b[0m    \x1b[1;31m^\x1b[0m while parsing a comma between elements of an array"
+        );
+    }
+}
@@ -7,10 +7,14 @@ use crate::parser::Parser;
 use crate::token_set::TokenSet;
 use crate::ts;
 
+mod command;
 mod expr;
 mod ident;
+mod param;
 mod path;
+mod pattern;
 mod stmt;
+mod types;
 mod value;
 
 pub(crate) fn repl_line(p: &mut Parser) -> CompletedMarker {
@@ -22,3 +26,29 @@ pub(crate) fn repl_line(p: &mut Parser) -> CompletedMarker {
 
     m.complete(p, SyntaxKind::SourceFile)
 }
+
+pub(crate) fn source_file(p: &mut Parser) -> CompletedMarker {
+    let m = p.start();
+
+    loop {
+        while p.maybe_at(TokenKind::Semicolon) {
+            p.bump(TokenKind::Semicolon);
+        }
+
+        if p.at_eof() {
+            break;
+        }
+
+        stmt::stmt(p);
+
+        // Statements are separated by a newline or a semicolon.
+        if p.at_eof() || p.maybe_at(TokenKind::Semicolon) || p.at_start_of_line() {
+            continue;
+        }
+
+        p.add_expected_kind(TokenKind::Semicolon);
+        p.error_skipping_line(ParseErrorContext::SourceFileSeparator);
+    }
+
+    m.complete(p, SyntaxKind::SourceFile)
+}
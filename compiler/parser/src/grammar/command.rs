@@ -0,0 +1,81 @@
+#[allow(clippy::wildcard_imports)]
+use super::*;
+
+const WORD_FIRSTS: TokenSet = ts![TokenKind::CommandWord, TokenKind::String];
+
+const REDIRECT_FIRSTS: TokenSet = ts![
+    TokenKind::RAngle,
+    TokenKind::DoubleRAngle,
+    TokenKind::LAngle,
+    TokenKind::StderrRAngle,
+    TokenKind::StderrToStdout,
+];
+
+pub(super) fn parse_command_expr(p: &mut Parser) -> CompletedMarker {
+    let m = p.start();
+    p.bump(TokenKind::Dollar);
+
+    parse_command(p, false);
+    while p.maybe_at(TokenKind::Pipe) {
+        p.bump(TokenKind::Pipe);
+        parse_command(p, true);
+    }
+
+    m.complete(p, SyntaxKind::CommandExpr)
+}
+
+/// Parses a command name followed by its arguments and redirections.
+///
+/// A command ends at the end of its line, except that the command after a `|` may start on the
+/// next line.
+fn parse_command(p: &mut Parser, after_pipe: bool) -> CompletedMarker {
+    let m = p.start();
+
+    let on_command_line = after_pipe || !p.at_start_of_line();
+    let at_word = p.at(TokenKind::CommandWord) || p.at(TokenKind::String);
+    if on_command_line && at_word {
+        p.bump_any();
+    } else if on_command_line && p.maybe_at(TokenKind::Error) {
+        p.error_with_recovery(ParseErrorContext::CommandName, ts![]);
+    } else {
+        p.error_missing(ParseErrorContext::CommandName);
+    }
+
+    while !p.at_start_of_line() {
+        if p.at_set(WORD_FIRSTS) {
+            p.bump_any();
+        } else if p.at_set(REDIRECT_FIRSTS) {
+            parse_redirect(p);
+        } else if p.maybe_at(TokenKind::Error) {
+            p.add_expected_kind(TokenKind::CommandWord);
+            p.add_expected_kind(TokenKind::String);
+            p.error_with_recovery(ParseErrorContext::CommandArg, ts![]);
+        } else {
+            break;
+        }
+    }
+
+    m.complete(p, SyntaxKind::Command)
+}
+
+/// Parses a redirection like `> out.txt`, or `2>&1`, which has no target.
+fn parse_redirect(p: &mut Parser) -> CompletedMarker {
+    let m = p.start();
+
+    let needs_target = !p.maybe_at(TokenKind::StderrToStdout);
+    p.bump_any();
+
+    if needs_target {
+        let on_command_line = !p.at_start_of_line();
+        let at_word = p.at(TokenKind::CommandWord) || p.at(TokenKind::String);
+        if on_command_line && at_word {
+            p.bump_any();
+        } else if on_command_line && p.maybe_at(TokenKind::Error) {
+            p.error_with_recovery(ParseErrorContext::RedirectTarget, ts![]);
+        } else {
+            p.error_missing(ParseErrorContext::RedirectTarget);
+        }
+    }
+
+    m.complete(p, SyntaxKind::Redirect)
+}
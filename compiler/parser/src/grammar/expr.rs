@@ -5,14 +5,60 @@ pub(crate) const EXPR_FIRSTS: TokenSet = ts![
     TokenKind::Integer,
     TokenKind::Fraction,
     TokenKind::String,
+    TokenKind::RawString,
+    TokenKind::MultilineString,
+    TokenKind::StringStart,
+    TokenKind::TrueKw,
+    TokenKind::FalseKw,
     TokenKind::Ident,
     TokenKind::Minus,
+    TokenKind::NotKw,
     TokenKind::LParen,
+    TokenKind::LBracket,
+    TokenKind::LBrace,
+    TokenKind::Pipe,
     TokenKind::IfKw,
+    TokenKind::Dollar,
 ];
 
+// Tokens that can start an argument of a call by juxtaposition (`ls dir`).
+// `-` is left out so that `a - 1` is still a subtraction, and `not` so that `f not x` is not
+// mistaken for passing `not x` to `f`.
+const APPLY_ARG_FIRSTS: TokenSet = ts![
+    TokenKind::Integer,
+    TokenKind::Fraction,
+    TokenKind::String,
+    TokenKind::RawString,
+    TokenKind::MultilineString,
+    TokenKind::StringStart,
+    TokenKind::TrueKw,
+    TokenKind::FalseKw,
+    TokenKind::Ident,
+    TokenKind::LParen,
+    TokenKind::LBracket,
+    TokenKind::LBrace,
+];
+
+// Tokens that can continue an interpolated string after an error inside an interpolation.
+const STRING_INTERP_RECOVERY_SET: TokenSet = ts![
+    TokenKind::InterpEnd,
+    TokenKind::InterpStart,
+    TokenKind::StringFragment,
+    TokenKind::StringEnd,
+];
+
+const MAP_KEY_FIRSTS: TokenSet = ts![TokenKind::Ident, TokenKind::String];
+
 enum BinaryOp {
     Pipe,
+    Or,
+    And,
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
     Add,
     Sub,
     Mul,
@@ -23,24 +69,97 @@ impl BinaryOp {
     fn binding_power(&self) -> (u8, u8) {
         match self {
             Self::Pipe => (1, 2),
-            Self::Add | Self::Sub => (3, 4),
-            Self::Mul | Self::Div => (5, 6),
+            Self::Or => (3, 4),
+            Self::And => (5, 6),
+            Self::Eq | Self::NotEq | Self::Lt | Self::LtEq | Self::Gt | Self::GtEq => (7, 8),
+            Self::Add | Self::Sub => (9, 10),
+            Self::Mul | Self::Div => (11, 12),
+        }
+    }
+
+    fn is_pipe(&self) -> bool {
+        matches!(self, Self::Pipe)
+    }
+
+    fn syntax_kind(&self) -> SyntaxKind {
+        match self {
+            Self::Pipe => SyntaxKind::PipeExpr,
+            Self::Or
+            | Self::And
+            | Self::Eq
+            | Self::NotEq
+            | Self::Lt
+            | Self::LtEq
+            | Self::Gt
+            | Self::GtEq
+            | Self::Add
+            | Self::Sub
+            | Self::Mul
+            | Self::Div => SyntaxKind::InfixExpr,
+        }
+    }
+
+    fn rhs_context(&self, context: ParseErrorContext) -> ParseErrorContext {
+        match self {
+            Self::Pipe => ParseErrorContext::PipeExprRhs,
+            Self::Or
+            | Self::And
+            | Self::Eq
+            | Self::NotEq
+            | Self::Lt
+            | Self::LtEq
+            | Self::Gt
+            | Self::GtEq
+            | Self::Add
+            | Self::Sub
+            | Self::Mul
+            | Self::Div => context,
         }
     }
 }
 
 enum UnaryOp {
     Neg,
+    Not,
 }
 
 impl UnaryOp {
     fn binding_power(&self) -> ((), u8) {
         match self {
-            Self::Neg => ((), 5),
+            Self::Neg => ((), 11),
+            // `not a == b` is `not (a == b)`, while `not a and b` is `(not a) and b`.
+            Self::Not => ((), 7),
+        }
+    }
+
+    fn token_kind(&self) -> TokenKind {
+        match self {
+            Self::Neg => TokenKind::Minus,
+            Self::Not => TokenKind::NotKw,
+        }
+    }
+}
+
+enum PostfixOp {
+    Call,
+    Index,
+    Field,
+    Apply,
+}
+
+impl PostfixOp {
+    fn binding_power(&self) -> (u8, ()) {
+        match self {
+            Self::Call | Self::Index | Self::Field => (15, ()),
+            Self::Apply => (13, ()),
         }
     }
 }
 
+// Arguments of a call by juxtaposition bind tighter than the juxtaposition itself,
+// so `f g x` passes both `g` and `x` to `f`.
+const APPLY_ARG_BINDING_POWER: u8 = 14;
+
 pub(super) fn parse_expr(p: &mut Parser, context: ParseErrorContext) -> Option<CompletedMarker> {
     parse_expr_with_recovery(p, ts![], context)
 }
@@ -54,6 +173,15 @@ fn parse_expr_with_recovery(
 }
 
 const SUPPORTED_OPERATORS: TokenSet = ts![
+    TokenKind::Pipe,
+    TokenKind::OrKw,
+    TokenKind::AndKw,
+    TokenKind::DoubleEquals,
+    TokenKind::BangEquals,
+    TokenKind::LAngle,
+    TokenKind::LAngleEquals,
+    TokenKind::RAngle,
+    TokenKind::RAngleEquals,
     TokenKind::Plus,
     TokenKind::Minus,
     TokenKind::Star,
@@ -66,9 +194,49 @@ fn parse_expr_with_binding_power(
     recovery_set: TokenSet,
     context: ParseErrorContext,
 ) -> Option<CompletedMarker> {
+    parse_expr_with_options(p, minimum_binding_power, recovery_set, context, true)
+}
+
+/// Parses an expression, where `allow_apply` is whether a path in it can be called by
+/// juxtaposition.
+fn parse_expr_with_options(
+    p: &mut Parser,
+    minimum_binding_power: u8,
+    recovery_set: TokenSet,
+    context: ParseErrorContext,
+    allow_apply: bool,
+) -> Option<CompletedMarker> {
+    // Only a bare path can be called by juxtaposition, e.g. `ls dir` or `foo::bar 1`.
+    let mut can_apply = allow_apply && p.maybe_at(TokenKind::Ident);
     let mut lhs = parse_lhs(p, recovery_set, context)?;
 
     loop {
+        // A `(` or `[` directly after an expression is a call or an index,
+        // while `f (1, 2)` and `f [1, 2]` pass a tuple or an array to `f`.
+        let postfix_op = if p.maybe_at_adjacent(TokenKind::LParen) {
+            Some(PostfixOp::Call)
+        } else if p.maybe_at_adjacent(TokenKind::LBracket) {
+            Some(PostfixOp::Index)
+        } else if p.maybe_at(TokenKind::Dot) {
+            Some(PostfixOp::Field)
+        } else if can_apply && p.at_set_on_same_line(APPLY_ARG_FIRSTS) {
+            Some(PostfixOp::Apply)
+        } else {
+            None
+        };
+        can_apply = false;
+
+        if let Some(op) = postfix_op {
+            let (left_binding_power, ()) = op.binding_power();
+
+            if left_binding_power < minimum_binding_power {
+                break;
+            }
+
+            lhs = parse_postfix_expr(p, lhs, op, recovery_set);
+            continue;
+        }
+
         if !p.at_set(SUPPORTED_OPERATORS) {
             // We’re not at an operator we recognize;
             // we don’t know what to do next, so we return and let caller decide.
@@ -83,6 +251,22 @@ fn parse_expr_with_binding_power(
             BinaryOp::Mul
         } else if p.at(TokenKind::Slash) {
             BinaryOp::Div
+        } else if p.at(TokenKind::DoubleEquals) {
+            BinaryOp::Eq
+        } else if p.at(TokenKind::BangEquals) {
+            BinaryOp::NotEq
+        } else if p.at(TokenKind::LAngle) {
+            BinaryOp::Lt
+        } else if p.at(TokenKind::LAngleEquals) {
+            BinaryOp::LtEq
+        } else if p.at(TokenKind::RAngle) {
+            BinaryOp::Gt
+        } else if p.at(TokenKind::RAngleEquals) {
+            BinaryOp::GtEq
+        } else if p.at(TokenKind::AndKw) {
+            BinaryOp::And
+        } else if p.at(TokenKind::OrKw) {
+            BinaryOp::Or
         } else if p.at(TokenKind::Pipe) {
             BinaryOp::Pipe
         } else {
@@ -99,11 +283,20 @@ fn parse_expr_with_binding_power(
         p.bump_any();
 
         let m = lhs.precede(p);
-        let parsed_rhs =
-            parse_expr_with_binding_power(p, right_binding_power, recovery_set, context).is_some();
-        lhs = m.complete(p, SyntaxKind::InfixExpr);
-
-        if !parsed_rhs {
+        let parsed_rhs = parse_expr_with_options(
+            p,
+            right_binding_power,
+            recovery_set,
+            op.rhs_context(context),
+            allow_apply,
+        )
+        .is_some();
+        lhs = m.complete(p, op.syntax_kind());
+
+        // After a bad operand of a pipe, a `|` continues the pipeline rather than starting a
+        // lambda, so `x | ) | f` still pipes into `f`.
+        let continues_pipeline = op.is_pipe() && p.maybe_at(TokenKind::Pipe);
+        if !parsed_rhs && !continues_pipeline {
             break;
         }
     }
@@ -111,6 +304,84 @@ fn parse_expr_with_binding_power(
     Some(lhs)
 }
 
+fn parse_postfix_expr(
+    p: &mut Parser,
+    lhs: CompletedMarker,
+    op: PostfixOp,
+    recovery_set: TokenSet,
+) -> CompletedMarker {
+    let m = lhs.precede(p);
+
+    match op {
+        PostfixOp::Call => {
+            parse_paren_arg_list(p);
+            m.complete(p, SyntaxKind::CallExpr)
+        }
+        PostfixOp::Apply => {
+            let args_m = p.start();
+            while p.at_set_on_same_line(APPLY_ARG_FIRSTS) {
+                parse_expr_with_binding_power(
+                    p,
+                    APPLY_ARG_BINDING_POWER,
+                    recovery_set,
+                    ParseErrorContext::ArgListExpr,
+                );
+            }
+            args_m.complete(p, SyntaxKind::ArgList);
+            m.complete(p, SyntaxKind::CallExpr)
+        }
+        PostfixOp::Index => {
+            p.bump(TokenKind::LBracket);
+            parse_expr_with_recovery(
+                p,
+                ts![TokenKind::RBracket],
+                ParseErrorContext::IndexExprExpr,
+            );
+            p.expect(
+                TokenKind::RBracket,
+                ParseErrorContext::IndexExprRightBracket,
+            );
+            m.complete(p, SyntaxKind::IndexExpr)
+        }
+        PostfixOp::Field => {
+            p.bump(TokenKind::Dot);
+            ident::parse_ident(p, ParseErrorContext::FieldExprName, ts![]);
+            m.complete(p, SyntaxKind::FieldExpr)
+        }
+    }
+}
+
+fn parse_paren_arg_list(p: &mut Parser) -> CompletedMarker {
+    let args_m = p.start();
+    p.bump(TokenKind::LParen);
+
+    loop {
+        if should_stop(p) {
+            break;
+        }
+
+        parse_expr(p, ParseErrorContext::ArgListExpr);
+
+        if should_stop(p) {
+            break;
+        }
+
+        p.expect_with_recovery(
+            TokenKind::Comma,
+            ParseErrorContext::ArgListComma,
+            EXPR_FIRSTS,
+        );
+    }
+
+    p.expect(TokenKind::RParen, ParseErrorContext::ArgListRightParen);
+
+    return args_m.complete(p, SyntaxKind::ArgList);
+
+    fn should_stop(p: &mut Parser) -> bool {
+        p.maybe_at(TokenKind::RParen) || p.at_eof() || p.at_top_level_token()
+    }
+}
+
 fn parse_lhs(
     p: &mut Parser,
     recovery_set: TokenSet,
@@ -120,16 +391,33 @@ fn parse_lhs(
         parse_int_literal(p)
     } else if p.at(TokenKind::Fraction) {
         parse_fraction_literal(p)
-    } else if p.at(TokenKind::String) {
+    } else if p.at(TokenKind::String)
+        || p.maybe_at(TokenKind::RawString)
+        || p.maybe_at(TokenKind::MultilineString)
+    {
         parse_string_literal(p)
+    } else if p.maybe_at(TokenKind::StringStart) {
+        parse_string_interp(p)
+    } else if p.at(TokenKind::TrueKw) || p.at(TokenKind::FalseKw) {
+        parse_bool_literal(p)
     } else if p.at(TokenKind::Ident) {
         parse_variable_ref(p)
     } else if p.at(TokenKind::Minus) {
-        parse_prefix_expr(p)
+        parse_prefix_expr(p, UnaryOp::Neg)
+    } else if p.at(TokenKind::NotKw) {
+        parse_prefix_expr(p, UnaryOp::Not)
     } else if p.at(TokenKind::LParen) {
         parse_paren_expr(p)
+    } else if p.at(TokenKind::LBracket) {
+        parse_array_expr(p)
+    } else if p.at(TokenKind::LBrace) {
+        parse_map_expr(p)
+    } else if p.at(TokenKind::Pipe) {
+        parse_lambda_expr(p)
     } else if p.at(TokenKind::IfKw) {
         parse_if_then_else_expr(p)
+    } else if p.at(TokenKind::Dollar) {
+        command::parse_command_expr(p)
     } else {
         p.error_with_recovery(context, recovery_set);
         return None;
@@ -150,12 +438,59 @@ pub(crate) fn parse_fraction_literal(p: &mut Parser) -> CompletedMarker {
     m.complete(p, SyntaxKind::FractionLiteral)
 }
 
+fn parse_bool_literal(p: &mut Parser) -> CompletedMarker {
+    let m = p.start();
+    p.bump_any();
+    m.complete(p, SyntaxKind::BoolLiteral)
+}
+
+/// Parses a plain, raw or multi-line string.
 pub(crate) fn parse_string_literal(p: &mut Parser) -> CompletedMarker {
     let m = p.start();
-    p.bump(TokenKind::String);
+    p.bump_any();
     m.complete(p, SyntaxKind::StringLiteral)
 }
 
+/// Parses a string with interpolations, like `"hello ${name}!"`.
+///
+/// The lexer splits the string into its text and the tokens of each interpolated expression.
+fn parse_string_interp(p: &mut Parser) -> CompletedMarker {
+    let m = p.start();
+    p.bump(TokenKind::StringStart);
+
+    loop {
+        if p.maybe_at(TokenKind::InterpStart) {
+            p.bump(TokenKind::InterpStart);
+            parse_expr_with_recovery(
+                p,
+                STRING_INTERP_RECOVERY_SET,
+                ParseErrorContext::StringInterpExpr,
+            );
+
+            if !p.at(TokenKind::InterpEnd) {
+                p.error_skipping_to(
+                    ParseErrorContext::StringInterpRightBrace,
+                    STRING_INTERP_RECOVERY_SET,
+                );
+            }
+            if p.maybe_at(TokenKind::InterpEnd) {
+                p.bump(TokenKind::InterpEnd);
+            }
+        } else if p.maybe_at(TokenKind::StringFragment) {
+            p.bump(TokenKind::StringFragment);
+        } else if p.maybe_at(TokenKind::StringEnd) {
+            p.bump(TokenKind::StringEnd);
+            break;
+        } else {
+            p.add_expected_kind(TokenKind::StringEnd);
+            p.error_with_recovery(ParseErrorContext::StringInterpEnd, ts![]);
+            break;
+        }
+    }
+
+    m.complete(p, SyntaxKind::StringInterp)
+}
+
 pub(crate) fn parse_variable_ref(p: &mut Parser) -> CompletedMarker {
     path::parse_path(
         p,
@@ -169,11 +504,16 @@ fn parse_if_then_else_expr(p: &mut Parser) -> CompletedMarker {
     let if_then_else_m = p.start();
     p.bump(TokenKind::IfKw);
 
+    // Without a `then`, `if test 2 else 3` is more likely missing it after `test` than calling
+    // `test` with `2`, so the condition only calls paths by juxtaposition when one follows.
+    let allow_apply = p.at_ahead(TokenKind::ThenKw, ts![TokenKind::ElseKw]);
     let if_m = p.start();
-    parse_expr_with_recovery(
+    parse_expr_with_options(
         p,
+        0,
         ts![TokenKind::ThenKw],
         ParseErrorContext::IfThenElseIfExpr,
+        allow_apply,
     );
     if_m.complete(p, SyntaxKind::IfExpr);
 
@@ -202,14 +542,22 @@ fn parse_if_then_else_expr(p: &mut Parser) -> CompletedMarker {
     if_then_else_m.complete(p, SyntaxKind::IfThenElseExpr)
 }
 
-fn parse_prefix_expr(p: &mut Parser) -> CompletedMarker {
+fn parse_lambda_expr(p: &mut Parser) -> CompletedMarker {
+    let m = p.start();
+
+    param::parse_lambda_params(p);
+    parse_expr(p, ParseErrorContext::LambdaExprBody);
+
+    m.complete(p, SyntaxKind::LambdaExpr)
+}
+
+fn parse_prefix_expr(p: &mut Parser, op: UnaryOp) -> CompletedMarker {
     let m = p.start();
 
-    let op = UnaryOp::Neg;
     let ((), right_binding_power) = op.binding_power();
 
     // Eat the operator’s token.
-    p.bump(TokenKind::Minus);
+    p.bump(op.token_kind());
 
     parse_expr_with_binding_power(
         p,
@@ -255,6 +603,94 @@ fn parse_paren_expr(p: &mut Parser) -> CompletedMarker {
     return paren_m.complete(p, kind);
 
     fn should_stop(p: &mut Parser) -> bool {
-        p.maybe_at(TokenKind::RParen) || p.at_eof()
+        p.maybe_at(TokenKind::RParen) || p.at_eof() || p.at_top_level_token()
+    }
+}
+
+fn parse_array_expr(p: &mut Parser) -> CompletedMarker {
+    let array_m = p.start();
+    p.bump(TokenKind::LBracket);
+
+    loop {
+        if should_stop(p) {
+            break;
+        }
+
+        parse_expr(p, ParseErrorContext::ArrayExprExpr);
+
+        if should_stop(p) {
+            break;
+        }
+
+        p.expect_with_recovery(
+            TokenKind::Comma,
+            ParseErrorContext::ArrayExprComma,
+            EXPR_FIRSTS,
+        );
+    }
+
+    p.expect(
+        TokenKind::RBracket,
+        ParseErrorContext::ArrayExprRightBracket,
+    );
+
+    return array_m.complete(p, SyntaxKind::ArrayExpr);
+
+    fn should_stop(p: &mut Parser) -> bool {
+        p.maybe_at(TokenKind::RBracket) || p.at_eof() || p.at_top_level_token()
+    }
+}
+
+fn parse_map_expr(p: &mut Parser) -> CompletedMarker {
+    let map_m = p.start();
+    p.bump(TokenKind::LBrace);
+
+    loop {
+        if should_stop(p) {
+            break;
+        }
+
+        parse_map_entry(p);
+
+        if should_stop(p) {
+            break;
+        }
+
+        p.expect_with_recovery(
+            TokenKind::Comma,
+            ParseErrorContext::MapExprComma,
+            MAP_KEY_FIRSTS,
+        );
+    }
+
+    p.expect(TokenKind::RBrace, ParseErrorContext::MapExprRightBrace);
+
+    return map_m.complete(p, SyntaxKind::MapExpr);
+
+    fn should_stop(p: &mut Parser) -> bool {
+        p.maybe_at(TokenKind::RBrace) || p.at_eof() || p.at_top_level_token()
+    }
+}
+
+fn parse_map_entry(p: &mut Parser) -> CompletedMarker {
+    let entry_m = p.start();
+
+    if p.at(TokenKind::Ident) || p.at(TokenKind::String) {
+        p.bump_any();
+    } else {
+        p.error_with_recovery(ParseErrorContext::MapEntryKey, ts![TokenKind::Colon]);
     }
+
+    p.expect_with_recovery(
+        TokenKind::Colon,
+        ParseErrorContext::MapEntryColon,
+        EXPR_FIRSTS,
+    );
+    parse_expr_with_recovery(
+        p,
+        ts![TokenKind::Comma, TokenKind::RBrace],
+        ParseErrorContext::MapEntryExpr,
+    );
+
+    entry_m.complete(p, SyntaxKind::MapEntry)
 }
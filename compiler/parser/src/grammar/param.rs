@@ -0,0 +1,40 @@
+#[allow(clippy::wildcard_imports)]
+use super::*;
+use crate::grammar::expr::EXPR_FIRSTS;
+use crate::grammar::pattern::PATTERN_FIRSTS;
+
+pub(super) fn parse_value_def_params(p: &mut Parser) -> CompletedMarker {
+    let m = p.start();
+
+    while p.at_set(PATTERN_FIRSTS) {
+        let m = p.start();
+        pattern::parse_pattern(p, ParseErrorContext::ParamPattern, ts![TokenKind::Equals]);
+        m.complete(p, SyntaxKind::Param);
+    }
+
+    m.complete(p, SyntaxKind::ParamList)
+}
+
+pub(super) fn parse_lambda_params(p: &mut Parser) -> CompletedMarker {
+    let m = p.start();
+    p.bump(TokenKind::Pipe);
+
+    while !p.maybe_at(TokenKind::Pipe) && !p.at_eof() && !p.at_top_level_token() {
+        // Unlike in a value definition, the `|` delimits the parameters of a lambda,
+        // so they can be annotated without parentheses, e.g. `|x: Number| x + 1`.
+        let m = p.start();
+        pattern::parse_pattern(p, ParseErrorContext::ParamPattern, ts![TokenKind::Pipe]);
+        if p.at(TokenKind::Colon) {
+            types::parse_type_annotation(p, ts![TokenKind::Pipe]);
+        }
+        m.complete(p, SyntaxKind::Param);
+    }
+
+    p.expect_with_recovery(
+        TokenKind::Pipe,
+        ParseErrorContext::LambdaExprRightPipe,
+        EXPR_FIRSTS,
+    );
+
+    m.complete(p, SyntaxKind::ParamList)
+}
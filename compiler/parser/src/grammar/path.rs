@@ -23,6 +23,6 @@ pub(super) fn parse_path(
     return parent_m.complete(p, kind);
 
     fn should_stop(p: &mut Parser) -> bool {
-        !p.at_set(ts![TokenKind::DoubleColon, TokenKind::Colon]) || p.at_eof()
+        !p.maybe_at(TokenKind::DoubleColon) || p.at_eof()
     }
 }
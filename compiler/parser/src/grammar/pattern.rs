@@ -0,0 +1,83 @@
+#[allow(clippy::wildcard_imports)]
+use super::*;
+
+pub(crate) const PATTERN_FIRSTS: TokenSet = ts![TokenKind::Ident, TokenKind::LParen];
+
+pub(super) fn parse_pattern(p: &mut Parser, context: ParseErrorContext, recovery_set: TokenSet) {
+    if p.at(TokenKind::Ident) {
+        p.bump(TokenKind::Ident);
+    } else if p.at(TokenKind::LParen) {
+        parse_paren_pattern(p, recovery_set);
+    } else {
+        p.error_with_recovery(context, recovery_set);
+    }
+}
+
+fn parse_paren_pattern(p: &mut Parser, recovery_set: TokenSet) -> CompletedMarker {
+    let paren_m = p.start();
+    p.bump(TokenKind::LParen);
+
+    let mut arg_len = 0;
+    loop {
+        if should_stop(p, recovery_set) {
+            break;
+        }
+
+        let arg_m = p.start();
+        parse_pattern(
+            p,
+            ParseErrorContext::ParenPatternPattern,
+            recovery_set.union(ts![TokenKind::Comma, TokenKind::RParen]),
+        );
+        if p.at(TokenKind::Colon) {
+            types::parse_type_annotation(
+                p,
+                recovery_set.union(ts![TokenKind::Comma, TokenKind::RParen]),
+            );
+        }
+        arg_len += 1;
+
+        // Like `(1)` and `(1,)` in expressions, `(a)` and `(a,)` are only parenthesized,
+        // so their pattern is not a tuple argument.
+        let is_only_arg = arg_len == 1
+            && (should_stop(p, recovery_set)
+                || p.maybe_at(TokenKind::Comma) && p.nth_at(1, TokenKind::RParen));
+        if is_only_arg {
+            arg_m.abandon(p);
+        } else {
+            arg_m.complete(p, SyntaxKind::TuplePatternArg);
+        }
+
+        if should_stop(p, recovery_set) {
+            break;
+        }
+
+        p.expect_with_recovery(
+            TokenKind::Comma,
+            ParseErrorContext::ParenPatternComma,
+            PATTERN_FIRSTS,
+        );
+    }
+
+    p.expect_with_recovery(
+        TokenKind::RParen,
+        ParseErrorContext::ParenPatternRightParen,
+        recovery_set,
+    );
+
+    let kind = match arg_len {
+        0 => SyntaxKind::UnitPattern,
+        1 => SyntaxKind::ParenPattern,
+        _ => SyntaxKind::TuplePattern,
+    };
+    return paren_m.complete(p, kind);
+
+    // The recovery set holds whatever ends the surrounding pattern, e.g. the `|` after the
+    // parameters of a lambda, so an unclosed pattern doesn't swallow it.
+    // A comma is never the end of this pattern, since it separates its own arguments.
+    fn should_stop(p: &mut Parser, recovery_set: TokenSet) -> bool {
+        p.maybe_at(TokenKind::RParen)
+            || p.at_eof()
+            || p.at_set(recovery_set.minus(TokenKind::Comma))
+    }
+}
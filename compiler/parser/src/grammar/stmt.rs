@@ -4,6 +4,8 @@ use super::*;
 pub(super) fn stmt(p: &mut Parser) -> Option<CompletedMarker> {
     if p.at(TokenKind::LetKw) {
         Some(value::parse_value(p))
+    } else if p.maybe_at(TokenKind::Ident) && p.nth_at(1, TokenKind::Colon) {
+        Some(types::parse_type_signature(p))
     } else {
         expr::parse_expr(p, ParseErrorContext::TopLevelExpr)
     }
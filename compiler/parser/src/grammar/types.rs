@@ -0,0 +1,162 @@
+#[allow(clippy::wildcard_imports)]
+use super::*;
+
+const TYPE_FIRSTS: TokenSet = ts![
+    TokenKind::Ident,
+    TokenKind::LParen,
+    TokenKind::LBracket,
+    TokenKind::LBrace,
+];
+
+pub(super) fn parse_type_signature(p: &mut Parser) -> CompletedMarker {
+    let m = p.start();
+    p.bump(TokenKind::Ident);
+
+    parse_type_annotation(p, ts![]);
+
+    m.complete(p, SyntaxKind::TypeSignature)
+}
+
+pub(super) fn parse_type_annotation(p: &mut Parser, recovery_set: TokenSet) -> CompletedMarker {
+    let m = p.start();
+    p.bump(TokenKind::Colon);
+
+    parse_type(p, ParseErrorContext::TypeAnnotationType, recovery_set);
+
+    m.complete(p, SyntaxKind::TypeAnnotation)
+}
+
+fn parse_type(
+    p: &mut Parser,
+    context: ParseErrorContext,
+    recovery_set: TokenSet,
+) -> Option<CompletedMarker> {
+    let lhs = parse_type_lhs(p, context, recovery_set)?;
+
+    // Function types are right associative, so `A -> B -> C` is `A -> (B -> C)`.
+    if !p.at(TokenKind::Arrow) {
+        return Some(lhs);
+    }
+
+    let m = lhs.precede(p);
+    p.bump(TokenKind::Arrow);
+    parse_type(p, ParseErrorContext::FunctionTypeReturn, recovery_set);
+
+    Some(m.complete(p, SyntaxKind::FunctionType))
+}
+
+fn parse_type_lhs(
+    p: &mut Parser,
+    context: ParseErrorContext,
+    recovery_set: TokenSet,
+) -> Option<CompletedMarker> {
+    let cm = if p.at(TokenKind::Ident) {
+        path::parse_path(p, context, recovery_set, SyntaxKind::NamedType)
+    } else if p.at(TokenKind::LParen) {
+        parse_paren_type(p, recovery_set)
+    } else if p.at(TokenKind::LBracket) {
+        parse_array_type(p, recovery_set)
+    } else if p.at(TokenKind::LBrace) {
+        parse_map_type(p, recovery_set)
+    } else {
+        p.error_with_recovery(context, recovery_set);
+        return None;
+    };
+
+    Some(cm)
+}
+
+fn parse_paren_type(p: &mut Parser, recovery_set: TokenSet) -> CompletedMarker {
+    let paren_m = p.start();
+    p.bump(TokenKind::LParen);
+
+    let mut arg_len = 0;
+    loop {
+        if should_stop(p, recovery_set) {
+            break;
+        }
+
+        parse_type(
+            p,
+            ParseErrorContext::ParenTypeType,
+            recovery_set.union(ts![TokenKind::Comma, TokenKind::RParen]),
+        );
+        arg_len += 1;
+
+        if should_stop(p, recovery_set) {
+            break;
+        }
+
+        p.expect_with_recovery(
+            TokenKind::Comma,
+            ParseErrorContext::ParenTypeComma,
+            TYPE_FIRSTS,
+        );
+    }
+
+    p.expect_with_recovery(
+        TokenKind::RParen,
+        ParseErrorContext::ParenTypeRightParen,
+        recovery_set,
+    );
+
+    let kind = match arg_len {
+        0 => SyntaxKind::UnitType,
+        1 => SyntaxKind::ParenType,
+        _ => SyntaxKind::TupleType,
+    };
+    return paren_m.complete(p, kind);
+
+    // Like in patterns, the recovery set holds whatever ends the surrounding type annotation.
+    fn should_stop(p: &mut Parser, recovery_set: TokenSet) -> bool {
+        p.maybe_at(TokenKind::RParen)
+            || p.at_eof()
+            || p.at_set(recovery_set.minus(TokenKind::Comma))
+    }
+}
+
+fn parse_array_type(p: &mut Parser, recovery_set: TokenSet) -> CompletedMarker {
+    let m = p.start();
+    p.bump(TokenKind::LBracket);
+
+    parse_type(
+        p,
+        ParseErrorContext::ArrayTypeType,
+        recovery_set.plus(TokenKind::RBracket),
+    );
+    p.expect_with_recovery(
+        TokenKind::RBracket,
+        ParseErrorContext::ArrayTypeRightBracket,
+        recovery_set,
+    );
+
+    m.complete(p, SyntaxKind::ArrayType)
+}
+
+fn parse_map_type(p: &mut Parser, recovery_set: TokenSet) -> CompletedMarker {
+    let m = p.start();
+    p.bump(TokenKind::LBrace);
+
+    parse_type(
+        p,
+        ParseErrorContext::MapTypeKey,
+        recovery_set.union(ts![TokenKind::Colon, TokenKind::RBrace]),
+    );
+    p.expect_with_recovery(
+        TokenKind::Colon,
+        ParseErrorContext::MapTypeColon,
+        TYPE_FIRSTS,
+    );
+    parse_type(
+        p,
+        ParseErrorContext::MapTypeValue,
+        recovery_set.plus(TokenKind::RBrace),
+    );
+    p.expect_with_recovery(
+        TokenKind::RBrace,
+        ParseErrorContext::MapTypeRightBrace,
+        recovery_set,
+    );
+
+    m.complete(p, SyntaxKind::MapType)
+}
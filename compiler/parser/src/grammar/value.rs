@@ -1,12 +1,25 @@
 #[allow(clippy::wildcard_imports)]
 use super::*;
 use crate::grammar::expr::EXPR_FIRSTS;
+use crate::grammar::pattern::PATTERN_FIRSTS;
 
 pub(crate) fn parse_value(p: &mut Parser) -> CompletedMarker {
     let m = p.start();
     p.bump(TokenKind::LetKw);
 
-    ident::parse_ident(p, ParseErrorContext::ValueDefIdent, ts![TokenKind::Equals]);
+    // Only a value bound to a name can take parameters, e.g. `let add a b = a + b`.
+    let binds_name = p.maybe_at(TokenKind::Ident);
+    pattern::parse_pattern(
+        p,
+        ParseErrorContext::ValueDefPattern,
+        ts![TokenKind::Equals],
+    );
+    if binds_name && p.at_set(PATTERN_FIRSTS) {
+        param::parse_value_def_params(p);
+    }
+    if p.at(TokenKind::Colon) {
+        types::parse_type_annotation(p, ts![TokenKind::Equals]);
+    }
     p.expect_with_recovery(
         TokenKind::Equals,
         ParseErrorContext::ValueDefEquals,
@@ -4,16 +4,20 @@ use std::fmt::Write as _;
 use psh_lexer::Lexer;
 use psh_syntax::SyntaxNode;
 
-pub use crate::parser::ParseError;
 use crate::parser::Parser;
+pub use crate::parser::{ParseError, ParseErrorContext, ParseErrorKind};
 use crate::sink::Sink;
 use crate::source::Source;
+pub use crate::suggestion::{apply_suggestions, Suggestion};
+pub use psh_lexer::TokenKind;
 
+pub mod diagnostics;
 mod event;
 mod grammar;
 mod parser;
 mod sink;
 mod source;
+mod suggestion;
 mod token_set;
 
 #[cfg(test)]
@@ -30,6 +34,20 @@ pub fn parse_repl_line(input: &str) -> Parse {
     sink.finish()
 }
 
+/// Parses a whole file of statements, separated by newlines or semicolons.
+///
+/// The file may start with a shebang line, like `#!/usr/bin/env psh`.
+#[must_use]
+pub fn parse_source_file(input: &str) -> Parse {
+    let tokens: Vec<_> = Lexer::new(input).collect();
+    let source = Source::new(&tokens);
+    let parser = Parser::new(source);
+    let events = parser.parse_source_file();
+    let sink = Sink::new(&tokens, events);
+
+    sink.finish()
+}
+
 pub struct Parse {
     green_node: GreenNode,
     errors: Vec<ParseError>,
@@ -61,4 +79,16 @@ impl Parse {
     pub fn errors(&self) -> &[ParseError] {
         &self.errors
     }
+
+    /// Whether the input ended while the parser was still expecting more, like after `1 +`.
+    ///
+    /// An interactive prompt can use this to ask for another line instead of reporting errors.
+    #[must_use]
+    pub fn is_incomplete(&self) -> bool {
+        let end = self.syntax().text_range().end();
+
+        self.errors
+            .last()
+            .is_some_and(|error| error.is_missing_at(end))
+    }
 }
@@ -1,16 +1,18 @@
 use std::mem;
 
+use itertools::Itertools;
+
 use psh_lexer::{Token, TokenKind};
 use psh_syntax::SyntaxKind;
+use text_size::{TextRange, TextSize};
 
 use crate::event::Event;
 use crate::parser::marker::CompletedMarker;
-use crate::parser::parse_error::ParseErrorKind;
 use crate::source::Source;
+use crate::suggestion::Suggestion;
 use crate::token_set::TokenSet;
 use crate::{grammar, ts};
-pub use parse_error::ParseError;
-pub(crate) use parse_error::ParseErrorContext;
+pub use parse_error::{ParseError, ParseErrorContext, ParseErrorKind};
 
 use self::marker::Marker;
 
@@ -42,6 +44,11 @@ impl<'t, 'input> Parser<'t, 'input> {
         self.events
     }
 
+    pub(crate) fn parse_source_file(mut self) -> Vec<Event> {
+        grammar::source_file(&mut self);
+        self.events
+    }
+
     pub(crate) fn start(&mut self) -> Marker {
         let pos = self.events.len();
         self.events.push(Event::Placeholder);
@@ -50,18 +57,49 @@ impl<'t, 'input> Parser<'t, 'input> {
     }
 
     pub(crate) fn at(&mut self, kind: TokenKind) -> bool {
+        self.add_expected_kind(kind);
+        self.maybe_at(kind)
+    }
+
+    /// Lists `kind` among the tokens the next error says were expected, without checking for it.
+    pub(crate) fn add_expected_kind(&mut self, kind: TokenKind) {
         self.expected_kinds.push(kind);
-        self.source.peek_nth_kind(0) == Some(kind)
     }
 
     pub(crate) fn maybe_at(&mut self, kind: TokenKind) -> bool {
         self.source.peek_nth_kind(0) == Some(kind)
     }
 
+    pub(crate) fn nth_at(&mut self, n: usize, kind: TokenKind) -> bool {
+        self.source.peek_nth_kind(n) == Some(kind)
+    }
+
+    pub(crate) fn maybe_at_adjacent(&mut self, kind: TokenKind) -> bool {
+        !self.source.peek_is_preceded_by_trivia() && self.maybe_at(kind)
+    }
+
+    pub(crate) fn at_set_on_same_line(&mut self, set: TokenSet) -> bool {
+        !self.source.peek_is_preceded_by_newline() && self.at_set(set)
+    }
+
+    pub(crate) fn at_start_of_line(&mut self) -> bool {
+        self.source.peek_is_preceded_by_newline()
+    }
+
     pub(crate) fn at_set(&mut self, set: TokenSet) -> bool {
         self.source
             .peek_nth_kind(0)
-            .map_or(false, |k| set.contains(k))
+            .is_some_and(|k| set.contains(k))
+    }
+
+    /// Whether `kind` comes up before any token in `stop_set`, a top-level token or the end of
+    /// the input.
+    pub(crate) fn at_ahead(&mut self, kind: TokenKind, stop_set: TokenSet) -> bool {
+        let stop_set = stop_set.union(DEFAULT_RECOVERY_SET);
+        self.source
+            .kinds_ahead()
+            .find(|next| *next == kind || stop_set.contains(*next))
+            == Some(kind)
     }
 
     pub(crate) fn at_top_level_token(&mut self) -> bool {
@@ -121,6 +159,56 @@ impl<'t, 'input> Parser<'t, 'input> {
         context: ParseErrorContext,
         recovery_set: TokenSet,
     ) -> Option<CompletedMarker> {
+        self.push_error(context, recovery_set);
+
+        if !self.at_set(recovery_set) && !self.at_eof() {
+            let m = self.start();
+            self.bump_any();
+            return Some(m.complete(self, SyntaxKind::Error));
+        };
+        None
+    }
+
+    /// Reports that something is missing before the current token, without consuming it.
+    pub(crate) fn error_missing(&mut self, context: ParseErrorContext) {
+        let recovery_set = self.source.peek_nth_kind(0).map_or(ts![], |kind| ts![kind]);
+        self.push_error(context, recovery_set);
+    }
+
+    /// Reports an error at the current token and skips the rest of the statement on its line,
+    /// so that a single mistake is reported once rather than once per token.
+    pub(crate) fn error_skipping_line(&mut self, context: ParseErrorContext) {
+        self.push_error(context, DEFAULT_RECOVERY_SET);
+
+        if self.at_top_level_token() || self.at_eof() {
+            return;
+        }
+
+        let m = self.start();
+        while !self.at_eof() && !self.maybe_at(TokenKind::Semicolon) && !self.at_start_of_line() {
+            self.bump_any();
+        }
+        m.complete(self, SyntaxKind::Error);
+    }
+
+    /// Reports an error at the current token and skips everything up to the next token in
+    /// `recovery_set`, so that the tokens in between are not reported one by one.
+    pub(crate) fn error_skipping_to(&mut self, context: ParseErrorContext, recovery_set: TokenSet) {
+        self.push_error(context, recovery_set.union(DEFAULT_RECOVERY_SET));
+
+        let at_end = |p: &mut Self| p.at_set(recovery_set) || p.at_top_level_token() || p.at_eof();
+        if at_end(self) {
+            return;
+        }
+
+        let m = self.start();
+        while !at_end(self) {
+            self.bump_any();
+        }
+        m.complete(self, SyntaxKind::Error);
+    }
+
+    fn push_error(&mut self, context: ParseErrorContext, recovery_set: TokenSet) {
         let last_token_range = self.source.last_token_range().unwrap_or_default();
 
         let current_token = self.source.peek_nth_token(0);
@@ -132,6 +220,12 @@ impl<'t, 'input> Parser<'t, 'input> {
             (None, last_token_range)
         };
 
+        // The lexer has already reported what is wrong with an `Error` token.
+        if found == Some(TokenKind::Error) {
+            self.expected_kinds.clear();
+            return;
+        }
+
         let kind = match found {
             None => ParseErrorKind::Missing {
                 offset: range.end(),
@@ -147,17 +241,39 @@ impl<'t, 'input> Parser<'t, 'input> {
             }
         };
 
+        // A missing token goes straight after the token before it, whatever trivia is between.
+        // Nothing is suggested when something right before it is missing too and has no fix of
+        // its own, like the condition of a bare `if`, since inserting the token alone would not
+        // fix anything.
+        let suggestions = match (kind, context.insertion()) {
+            (ParseErrorKind::Missing { offset }, Some(text))
+                if !self.follows_unfixed_missing_error(offset) =>
+            {
+                let offset = self.source.previous_token_range().unwrap_or_default().end();
+                vec![Suggestion::insert(TextRange::empty(offset), text)]
+            }
+            _ => Vec::new(),
+        };
+
         self.events.push(Event::Error(ParseError {
-            expected: mem::take(&mut self.expected_kinds),
+            expected: mem::take(&mut self.expected_kinds)
+                .into_iter()
+                .unique()
+                .collect(),
             kind,
             context,
+            suggestions,
         }));
+    }
 
-        if !self.at_set(recovery_set) && !self.at_eof() {
-            let m = self.start();
-            self.bump_any();
-            return Some(m.complete(self, SyntaxKind::Error));
-        };
-        None
+    fn follows_unfixed_missing_error(&self, offset: TextSize) -> bool {
+        self.events
+            .iter()
+            .rev()
+            .find_map(|event| match event {
+                Event::Error(error) => Some(error),
+                _ => None,
+            })
+            .is_some_and(|error| error.is_missing_at(offset) && error.suggestions.is_empty())
     }
 }
@@ -33,6 +33,16 @@ impl Marker {
 
         CompletedMarker { pos: self.pos }
     }
+
+    pub(crate) fn abandon(mut self, p: &mut Parser) {
+        self.bomb.defuse();
+
+        // If nothing was parsed since the marker was started we can drop its placeholder,
+        // otherwise the sink skips over it.
+        if self.pos == p.events.len() - 1 {
+            assert_eq!(p.events.pop(), Some(Event::Placeholder));
+        }
+    }
 }
 
 pub(crate) struct CompletedMarker {
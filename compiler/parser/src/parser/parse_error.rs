@@ -1,47 +1,316 @@
 use itertools::Itertools;
-use psh_lexer::TokenKind;
+use psh_lexer::{LexError, LexErrorKind, TokenKind};
+
+use crate::Suggestion;
 use std::fmt;
 use text_size::{TextRange, TextSize};
 
+/// An error found while parsing: a token that is missing or one that should not be there.
 #[derive(Debug, Clone, PartialEq)]
 pub struct ParseError {
-    pub(super) expected: Vec<TokenKind>,
-    pub(super) kind: ParseErrorKind,
-    pub(super) context: ParseErrorContext,
+    pub(crate) expected: Vec<TokenKind>,
+    pub(crate) kind: ParseErrorKind,
+    pub(crate) context: ParseErrorContext,
+    pub(crate) suggestions: Vec<Suggestion>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub(crate) enum ParseErrorKind {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// The input ended, or reached a token that starts something else, at `offset` while one
+    /// of the expected tokens was still needed.
     Missing { offset: TextSize },
+    /// The token at `range` is none of the expected ones.
     Unexpected { found: TokenKind, range: TextRange },
+    /// The lexer found a problem with a token, like a string without its closing quote.
+    Lex(LexError),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub(crate) enum ParseErrorContext {
+/// What the parser was in the middle of when it found an error.
+///
+/// Each context has a stable [code](ParseErrorContext::code). Contexts may be added, but the
+/// code of an existing one never changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ParseErrorContext {
     PrefixExprExpr,
+    PipeExprRhs,
     ParenExprExpr,
     ParenExprComma,
     ParenExprRightParen,
+    ArrayExprExpr,
+    ArrayExprComma,
+    ArrayExprRightBracket,
+    MapExprComma,
+    MapExprRightBrace,
+    MapEntryKey,
+    MapEntryColon,
+    MapEntryExpr,
+    IndexExprExpr,
+    IndexExprRightBracket,
+    FieldExprName,
+    ArgListExpr,
+    ArgListComma,
+    ArgListRightParen,
+    StringInterpExpr,
+    StringInterpRightBrace,
+    StringInterpEnd,
     IfThenElseIfExpr,
     IfThenElseThenKw,
     IfThenElseThenExpr,
     IfThenElseElseKw,
     IfThenElseElseExpr,
-    ValueDefIdent,
+    ValueDefPattern,
     ValueDefEquals,
     ValueDefExpr,
+    LambdaExprRightPipe,
+    LambdaExprBody,
+    ParamPattern,
+    ParenPatternPattern,
+    ParenPatternComma,
+    ParenPatternRightParen,
+    TypeAnnotationType,
+    ParenTypeType,
+    ParenTypeComma,
+    ParenTypeRightParen,
+    ArrayTypeType,
+    ArrayTypeRightBracket,
+    MapTypeKey,
+    MapTypeColon,
+    MapTypeValue,
+    MapTypeRightBrace,
+    FunctionTypeReturn,
+    CommandName,
+    CommandArg,
+    RedirectTarget,
     VariableRef,
     TopLevelExpr,
+    SourceFileSeparator,
+    Token,
+}
+
+impl ParseError {
+    #[must_use]
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    #[must_use]
+    pub fn context(&self) -> ParseErrorContext {
+        self.context
+    }
+
+    /// The stable code of the error, like `P0007`. See [`ParseErrorContext::code`].
+    ///
+    /// Errors from the lexer have the code of their kind instead, like `L0001`.
+    #[must_use]
+    pub fn code(&self) -> &'static str {
+        match self.kind {
+            ParseErrorKind::Lex(error) => error.kind.code(),
+            _ => self.context.code(),
+        }
+    }
+
+    /// The range of the unexpected token, or an empty range where the missing one should be.
+    #[must_use]
+    pub fn range(&self) -> TextRange {
+        match self.kind {
+            ParseErrorKind::Missing { offset } => TextRange::empty(offset),
+            ParseErrorKind::Unexpected { range, .. }
+            | ParseErrorKind::Lex(LexError { range, .. }) => range,
+        }
+    }
+
+    /// The token that was found instead of one of the expected ones, or `None` if a token is
+    /// missing or the error is from the lexer.
+    #[must_use]
+    pub fn found(&self) -> Option<TokenKind> {
+        match self.kind {
+            ParseErrorKind::Missing { .. } | ParseErrorKind::Lex(_) => None,
+            ParseErrorKind::Unexpected { found, .. } => Some(found),
+        }
+    }
+
+    /// The tokens that could have been there instead, without duplicates. Errors from the lexer
+    /// expect nothing.
+    #[must_use]
+    pub fn expected(&self) -> &[TokenKind] {
+        &self.expected
+    }
+
+    /// A short description of the error without its position or context, like "found ‘)’, but
+    /// expected ‘;’".
+    #[must_use]
+    pub fn message(&self) -> String {
+        match self.kind {
+            ParseErrorKind::Missing { .. } => format!("missing expected {}", self.expected_list()),
+            ParseErrorKind::Unexpected { found, .. } => {
+                format!("found {found}, but expected {}", self.expected_list())
+            }
+            ParseErrorKind::Lex(error) => error.kind.to_string(),
+        }
+    }
+
+    /// Edits to the input that would fix the error. See [`crate::apply_suggestions`].
+    #[must_use]
+    pub fn suggestions(&self) -> &[Suggestion] {
+        &self.suggestions
+    }
+
+    /// Whether the input ended while the error still needed something: a missing token, or a
+    /// string without its closing quote.
+    pub(crate) fn is_missing_at(&self, offset: TextSize) -> bool {
+        match self.kind {
+            ParseErrorKind::Missing { offset: missing } => missing == offset,
+            ParseErrorKind::Lex(LexError {
+                kind: LexErrorKind::UnterminatedString,
+                range,
+            }) => range.end() == offset,
+            _ => false,
+        }
+    }
+
+    pub(crate) fn from_lex_error(error: LexError) -> Self {
+        Self {
+            expected: Vec::new(),
+            kind: ParseErrorKind::Lex(error),
+            context: ParseErrorContext::Token,
+            suggestions: Vec::new(),
+        }
+    }
+
+    fn expected_list(&self) -> ExpectedKinds<'_> {
+        ExpectedKinds(&self.expected)
+    }
 }
 
+/// Formats a list of expected tokens, like "integer, identifier or ‘(’".
+pub(crate) struct ExpectedKinds<'a>(&'a [TokenKind]);
+
 impl ParseErrorContext {
+    /// A code for the context that stays the same between versions of psh, for tools to match
+    /// on or link to.
     #[must_use]
-    fn context_name<'a>(self) -> &'a str {
+    pub fn code(self) -> &'static str {
+        match self {
+            ParseErrorContext::PrefixExprExpr => "P0001",
+            ParseErrorContext::PipeExprRhs => "P0002",
+            ParseErrorContext::ParenExprExpr => "P0003",
+            ParseErrorContext::ParenExprComma => "P0004",
+            ParseErrorContext::ParenExprRightParen => "P0005",
+            ParseErrorContext::ArrayExprExpr => "P0006",
+            ParseErrorContext::ArrayExprComma => "P0007",
+            ParseErrorContext::ArrayExprRightBracket => "P0008",
+            ParseErrorContext::MapExprComma => "P0009",
+            ParseErrorContext::MapExprRightBrace => "P0010",
+            ParseErrorContext::MapEntryKey => "P0011",
+            ParseErrorContext::MapEntryColon => "P0012",
+            ParseErrorContext::MapEntryExpr => "P0013",
+            ParseErrorContext::IndexExprExpr => "P0014",
+            ParseErrorContext::IndexExprRightBracket => "P0015",
+            ParseErrorContext::FieldExprName => "P0016",
+            ParseErrorContext::ArgListExpr => "P0017",
+            ParseErrorContext::ArgListComma => "P0018",
+            ParseErrorContext::ArgListRightParen => "P0019",
+            ParseErrorContext::StringInterpExpr => "P0020",
+            ParseErrorContext::StringInterpRightBrace => "P0021",
+            ParseErrorContext::StringInterpEnd => "P0022",
+            ParseErrorContext::IfThenElseIfExpr => "P0023",
+            ParseErrorContext::IfThenElseThenKw => "P0024",
+            ParseErrorContext::IfThenElseThenExpr => "P0025",
+            ParseErrorContext::IfThenElseElseKw => "P0026",
+            ParseErrorContext::IfThenElseElseExpr => "P0027",
+            ParseErrorContext::ValueDefPattern => "P0028",
+            ParseErrorContext::ValueDefEquals => "P0029",
+            ParseErrorContext::ValueDefExpr => "P0030",
+            ParseErrorContext::LambdaExprRightPipe => "P0031",
+            ParseErrorContext::LambdaExprBody => "P0032",
+            ParseErrorContext::ParamPattern => "P0033",
+            ParseErrorContext::ParenPatternPattern => "P0034",
+            ParseErrorContext::ParenPatternComma => "P0035",
+            ParseErrorContext::ParenPatternRightParen => "P0036",
+            ParseErrorContext::TypeAnnotationType => "P0037",
+            ParseErrorContext::ParenTypeType => "P0038",
+            ParseErrorContext::ParenTypeComma => "P0039",
+            ParseErrorContext::ParenTypeRightParen => "P0040",
+            ParseErrorContext::ArrayTypeType => "P0041",
+            ParseErrorContext::ArrayTypeRightBracket => "P0042",
+            ParseErrorContext::MapTypeKey => "P0043",
+            ParseErrorContext::MapTypeColon => "P0044",
+            ParseErrorContext::MapTypeValue => "P0045",
+            ParseErrorContext::MapTypeRightBrace => "P0046",
+            ParseErrorContext::FunctionTypeReturn => "P0047",
+            ParseErrorContext::CommandName => "P0048",
+            ParseErrorContext::CommandArg => "P0049",
+            ParseErrorContext::RedirectTarget => "P0050",
+            ParseErrorContext::VariableRef => "P0051",
+            ParseErrorContext::TopLevelExpr => "P0052",
+            ParseErrorContext::SourceFileSeparator => "P0053",
+            ParseErrorContext::Token => "P0054",
+        }
+    }
+
+    /// The text to insert after the previous token when the parser is missing the token this
+    /// context is looking for, for contexts where inserting that token completes what is being
+    /// parsed.
+    pub(crate) fn insertion(self) -> Option<&'static str> {
+        let text = match self {
+            ParseErrorContext::ParenExprComma
+            | ParseErrorContext::ArrayExprComma
+            | ParseErrorContext::MapExprComma
+            | ParseErrorContext::ArgListComma
+            | ParseErrorContext::ParenPatternComma
+            | ParseErrorContext::ParenTypeComma => ",",
+            ParseErrorContext::ParenExprRightParen
+            | ParseErrorContext::ArgListRightParen
+            | ParseErrorContext::ParenPatternRightParen
+            | ParseErrorContext::ParenTypeRightParen => ")",
+            ParseErrorContext::ArrayExprRightBracket
+            | ParseErrorContext::IndexExprRightBracket
+            | ParseErrorContext::ArrayTypeRightBracket => "]",
+            ParseErrorContext::MapExprRightBrace
+            | ParseErrorContext::StringInterpRightBrace
+            | ParseErrorContext::MapTypeRightBrace => "}",
+            ParseErrorContext::StringInterpEnd => "\"",
+            ParseErrorContext::MapEntryColon | ParseErrorContext::MapTypeColon => ":",
+            ParseErrorContext::IfThenElseThenKw => " then",
+            ParseErrorContext::IfThenElseElseKw => " else",
+            _ => return None,
+        };
+
+        Some(text)
+    }
+
+    /// What the parser was looking for, like "a comma between elements of an array".
+    #[must_use]
+    pub fn context_name(self) -> &'static str {
         match self {
             ParseErrorContext::PrefixExprExpr => "an expression after a prefix operator",
+            ParseErrorContext::PipeExprRhs => "the expression after a pipe",
             ParseErrorContext::ParenExprExpr => "an expression inside parentheses",
             ParseErrorContext::ParenExprComma => "a comma between expressions inside parentheses",
             ParseErrorContext::ParenExprRightParen => "a close parenthesis after an expression",
+            ParseErrorContext::ArrayExprExpr => "an element of an array",
+            ParseErrorContext::ArrayExprComma => "a comma between elements of an array",
+            ParseErrorContext::ArrayExprRightBracket => "a close bracket at the end of an array",
+            ParseErrorContext::MapExprComma => "a comma between entries of a map",
+            ParseErrorContext::MapExprRightBrace => "a close brace at the end of a map",
+            ParseErrorContext::MapEntryKey => "the key of a map entry",
+            ParseErrorContext::MapEntryColon => "the ‘:’ between the key and value of a map entry",
+            ParseErrorContext::MapEntryExpr => "the value of a map entry",
+            ParseErrorContext::IndexExprExpr => "the index expression inside brackets",
+            ParseErrorContext::IndexExprRightBracket => "a close bracket after an index expression",
+            ParseErrorContext::FieldExprName => "the field name after a ‘.’",
+            ParseErrorContext::ArgListExpr => "an argument in a function call",
+            ParseErrorContext::ArgListComma => "a comma between arguments in a function call",
+            ParseErrorContext::ArgListRightParen => {
+                "a close parenthesis at the end of an argument list"
+            }
+            ParseErrorContext::StringInterpExpr => "the expression inside a ‘${’ in a string",
+            ParseErrorContext::StringInterpRightBrace => {
+                "the ‘}’ closing an interpolation in a string"
+            }
+            ParseErrorContext::StringInterpEnd => "the closing quote of a string",
             ParseErrorContext::IfThenElseIfExpr => {
                 "the conditional expression in an if-then-else expression"
             }
@@ -57,11 +326,35 @@ impl ParseErrorContext {
             ParseErrorContext::IfThenElseElseExpr => {
                 "the `else` expression in an if-then-else expression"
             }
-            ParseErrorContext::ValueDefIdent => "the name in a value definition",
+            ParseErrorContext::ValueDefPattern => "the name or pattern in a value definition",
             ParseErrorContext::ValueDefEquals => "the ‘=’ in a value definition",
             ParseErrorContext::ValueDefExpr => "the expression in a value definition",
+            ParseErrorContext::LambdaExprRightPipe => "the ‘|’ after the parameters of a lambda",
+            ParseErrorContext::LambdaExprBody => "the body of a lambda",
+            ParseErrorContext::ParamPattern => "a parameter",
+            ParseErrorContext::ParenPatternPattern => "a pattern inside parentheses",
+            ParseErrorContext::ParenPatternComma => "a comma between patterns inside parentheses",
+            ParseErrorContext::ParenPatternRightParen => "a close parenthesis after a pattern",
+            ParseErrorContext::TypeAnnotationType => "the type in a type annotation",
+            ParseErrorContext::ParenTypeType => "a type inside parentheses",
+            ParseErrorContext::ParenTypeComma => "a comma between types inside parentheses",
+            ParseErrorContext::ParenTypeRightParen => "a close parenthesis after a type",
+            ParseErrorContext::ArrayTypeType => "the element type of an array type",
+            ParseErrorContext::ArrayTypeRightBracket => "a close bracket after an array type",
+            ParseErrorContext::MapTypeKey => "the key type of a map type",
+            ParseErrorContext::MapTypeColon => {
+                "the ‘:’ between the key and value types of a map type"
+            }
+            ParseErrorContext::MapTypeValue => "the value type of a map type",
+            ParseErrorContext::MapTypeRightBrace => "a close brace after a map type",
+            ParseErrorContext::FunctionTypeReturn => "the return type of a function type",
             ParseErrorContext::VariableRef => "a variable reference",
+            ParseErrorContext::CommandName => "the name of a command",
+            ParseErrorContext::CommandArg => "an argument to a command",
+            ParseErrorContext::RedirectTarget => "the file to redirect to or from",
             ParseErrorContext::TopLevelExpr => "a top level expression",
+            ParseErrorContext::SourceFileSeparator => "a newline or ‘;’ between statements",
+            ParseErrorContext::Token => "a token",
         }
     }
 }
@@ -71,6 +364,7 @@ impl fmt::Display for ParseError {
         let context_name = self.context.context_name();
 
         match self.kind {
+            ParseErrorKind::Lex(error) => return write!(f, "{error}"),
             ParseErrorKind::Missing { offset } => {
                 write!(f, "error at position {offset:?}")?;
                 write!(f, " while parsing {context_name}. ")?;
@@ -88,11 +382,13 @@ impl fmt::Display for ParseError {
             }
         }
 
-        //
-        // Expected
-        //
+        write!(f, "{}", self.expected_list())
+    }
+}
 
-        let vec = self.expected.iter().unique().collect::<Vec<_>>();
+impl fmt::Display for ExpectedKinds<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let vec = self.0.iter().unique().collect::<Vec<_>>();
         let num_expected = vec.len();
         let is_first = |idx| idx == 0;
         let is_last = |idx| idx == num_expected - 1;
@@ -111,6 +407,63 @@ impl fmt::Display for ParseError {
     }
 }
 
+/// Serializes an error as its code, context, message, byte range, the tokens found and expected,
+/// with tokens written as they are in messages, and its suggestions.
+#[cfg(feature = "serde")]
+impl serde::Serialize for ParseError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        #[derive(serde::Serialize)]
+        struct Range {
+            start: u32,
+            end: u32,
+        }
+
+        impl From<TextRange> for Range {
+            fn from(range: TextRange) -> Self {
+                Self {
+                    start: range.start().into(),
+                    end: range.end().into(),
+                }
+            }
+        }
+
+        #[derive(serde::Serialize)]
+        struct SerializedSuggestion<'a> {
+            range: Range,
+            replacement: &'a str,
+        }
+
+        let mut state = serializer.serialize_struct("ParseError", 7)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("context", self.context.context_name())?;
+        state.serialize_field("message", &self.message())?;
+        state.serialize_field("range", &Range::from(self.range()))?;
+        state.serialize_field("found", &self.found().map(|kind| kind.to_string()))?;
+        state.serialize_field(
+            "expected",
+            &self
+                .expected
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+        )?;
+        state.serialize_field(
+            "suggestions",
+            &self
+                .suggestions
+                .iter()
+                .map(|suggestion| SerializedSuggestion {
+                    range: Range::from(suggestion.range),
+                    replacement: &suggestion.replacement,
+                })
+                .collect::<Vec<_>>(),
+        )?;
+        state.end()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -121,6 +474,7 @@ mod tests {
             expected,
             kind,
             context: ParseErrorContext::ParenExprExpr,
+            suggestions: Vec::new(),
         };
 
         assert_eq!(format!("{error}"), output);
@@ -186,4 +540,57 @@ mod tests {
             "error at position 1 while parsing an expression inside parentheses. Missing expected ‘+’ or ‘-’",
         );
     }
+
+    #[test]
+    fn accessors_of_unexpected_token() {
+        let parse = crate::parse_repl_line("[1, then]");
+        let error = &parse.errors()[0];
+
+        assert_eq!(error.context(), ParseErrorContext::ArrayExprExpr);
+        assert_eq!(error.code(), "P0006");
+        assert_eq!(error.found(), Some(TokenKind::ThenKw));
+        assert_eq!(error.range(), TextRange::new(4.into(), 8.into()));
+        assert!(error.expected().contains(&TokenKind::Integer));
+    }
+
+    #[test]
+    fn accessors_of_missing_token() {
+        let parse = crate::parse_repl_line("(1");
+        let error = &parse.errors()[0];
+
+        assert_eq!(error.context(), ParseErrorContext::ParenExprRightParen);
+        assert_eq!(
+            error.kind(),
+            ParseErrorKind::Missing {
+                offset: TextSize::from(2)
+            }
+        );
+        assert_eq!(error.found(), None);
+        assert_eq!(error.range(), TextRange::empty(2.into()));
+        assert_eq!(error.expected(), [TokenKind::RParen]);
+        assert_eq!(
+            error.suggestions(),
+            [Suggestion::insert(TextRange::empty(2.into()), ")")]
+        );
+    }
+
+    #[test]
+    fn accessors_of_lex_error() {
+        let parse = crate::parse_repl_line("1 + \"a\\qb\"");
+        let error = &parse.errors()[0];
+
+        assert_eq!(error.context(), ParseErrorContext::Token);
+        assert_eq!(error.code(), "L0002");
+        assert_eq!(error.message(), "unknown escape ‘\\q’");
+        assert_eq!(error.found(), None);
+        assert_eq!(error.range(), TextRange::new(6.into(), 8.into()));
+        assert!(error.expected().is_empty());
+    }
+
+    #[test]
+    fn codes_are_stable() {
+        assert_eq!(ParseErrorContext::PrefixExprExpr.code(), "P0001");
+        assert_eq!(ParseErrorContext::ArrayExprComma.code(), "P0007");
+        assert_eq!(ParseErrorContext::SourceFileSeparator.code(), "P0053");
+    }
 }
@@ -80,10 +80,14 @@ impl<'t, 'input> Sink<'t, 'input> {
     }
 
     fn token(&mut self) {
-        let Token { kind, text, .. } = self.tokens[self.cursor];
+        let Token {
+            kind, text, errors, ..
+        } = &self.tokens[self.cursor];
 
         self.builder
-            .token(PshLanguage::kind_to_raw(kind.into()), text);
+            .token(PshLanguage::kind_to_raw((*kind).into()), text);
+        self.errors
+            .extend(errors.iter().copied().map(ParseError::from_lex_error));
 
         self.cursor += 1;
     }
@@ -25,19 +25,47 @@ impl<'t, 'input> Source<'t, 'input> {
         self.peek_kind_raw(skip)
     }
 
-    pub(crate) fn peek_nth_token(&mut self, skip: usize) -> Option<&Token> {
+    pub(crate) fn peek_nth_token(&mut self, skip: usize) -> Option<&Token<'input>> {
         self.peek_token_raw(skip)
     }
 
+    /// The kinds of the current token and every token after it, without trivia.
+    pub(crate) fn kinds_ahead(&mut self) -> impl Iterator<Item = TokenKind> + '_ {
+        self.eat_trivia();
+
+        self.tokens[self.cursor..]
+            .iter()
+            .map(|Token { kind, .. }| *kind)
+            .filter(|kind| !kind.is_trivia())
+    }
+
+    pub(crate) fn peek_is_preceded_by_trivia(&mut self) -> bool {
+        self.preceding_trivia().next().is_some()
+    }
+
+    pub(crate) fn peek_is_preceded_by_newline(&mut self) -> bool {
+        self.preceding_trivia()
+            .any(|Token { text, .. }| text.contains('\n'))
+    }
+
     pub(crate) fn last_token_range(&self) -> Option<TextRange> {
         self.tokens.last().map(|Token { range, .. }| *range)
     }
 
+    /// The range of the last token that was not trivia before the current one.
+    pub(crate) fn previous_token_range(&self) -> Option<TextRange> {
+        self.tokens[..self.cursor]
+            .iter()
+            .rev()
+            .find(|Token { kind, .. }| !kind.is_trivia())
+            .map(|Token { range, .. }| *range)
+    }
+
     fn peek_kind_raw(&mut self, skip: usize) -> Option<TokenKind> {
         self.peek_token_raw(skip).map(|Token { kind, .. }| *kind)
     }
 
-    fn peek_token_raw(&mut self, skip: usize) -> Option<&Token> {
+    fn peek_token_raw(&mut self, skip: usize) -> Option<&Token<'input>> {
         self.eat_trivia();
 
         let mut cursor = self.cursor;
@@ -59,6 +87,15 @@ impl<'t, 'input> Source<'t, 'input> {
         None
     }
 
+    fn preceding_trivia(&mut self) -> impl Iterator<Item = &Token<'input>> {
+        self.eat_trivia();
+
+        self.tokens[..self.cursor]
+            .iter()
+            .rev()
+            .take_while(|Token { kind, .. }| kind.is_trivia())
+    }
+
     fn eat_trivia(&mut self) {
         while self.at_trivia() {
             self.cursor += 1;
@@ -69,7 +106,7 @@ impl<'t, 'input> Source<'t, 'input> {
         self.tokens
             .get(self.cursor)
             .map(|Token { kind, .. }| *kind)
-            .map_or(false, TokenKind::is_trivia)
+            .is_some_and(TokenKind::is_trivia)
     }
 }
 
@@ -113,4 +150,45 @@ mod tests {
 
         assert_eq!(source.peek_nth_kind(1), Some(TokenKind::Ident));
     }
+
+    #[test]
+    fn peek_is_preceded_by_trivia_at_start() {
+        let input = "f(x)";
+        let tokens: Vec<_> = Lexer::new(input).collect();
+        let mut source = Source::new(&tokens);
+
+        assert!(!source.peek_is_preceded_by_trivia());
+    }
+
+    #[test]
+    fn peek_is_preceded_by_trivia_adjacent() {
+        let input = "f(x)";
+        let tokens: Vec<_> = Lexer::new(input).collect();
+        let mut source = Source::new(&tokens);
+        source.next_token();
+
+        assert!(!source.peek_is_preceded_by_trivia());
+    }
+
+    #[test]
+    fn peek_is_preceded_by_trivia_whitespace() {
+        let input = "f (x)";
+        let tokens: Vec<_> = Lexer::new(input).collect();
+        let mut source = Source::new(&tokens);
+        source.next_token();
+
+        assert!(source.peek_is_preceded_by_trivia());
+        assert!(!source.peek_is_preceded_by_newline());
+    }
+
+    #[test]
+    fn peek_is_preceded_by_newline_after_comment() {
+        let input = "f -- call f\n x";
+        let tokens: Vec<_> = Lexer::new(input).collect();
+        let mut source = Source::new(&tokens);
+        source.next_token();
+
+        assert!(source.peek_is_preceded_by_trivia());
+        assert!(source.peek_is_preceded_by_newline());
+    }
 }
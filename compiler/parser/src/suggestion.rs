@@ -0,0 +1,95 @@
+use text_size::TextRange;
+
+/// An edit to the input that fixes a parse error, like inserting a missing `)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    /// The range to replace, which is empty for an insertion.
+    pub range: TextRange,
+    pub replacement: String,
+}
+
+impl Suggestion {
+    pub(crate) fn insert(range: TextRange, text: &str) -> Self {
+        Self {
+            range,
+            replacement: text.to_string(),
+        }
+    }
+}
+
+/// Applies `suggestions` to `input`, in the order of where they are in it.
+///
+/// Insertions at the same offset are applied in the order they are given. A suggestion that
+/// overlaps one before it is skipped, since the text it would replace has already changed.
+#[must_use]
+pub fn apply_suggestions<'a>(
+    input: &str,
+    suggestions: impl IntoIterator<Item = &'a Suggestion>,
+) -> String {
+    let mut suggestions: Vec<_> = suggestions.into_iter().collect();
+    suggestions.sort_by_key(|suggestion| suggestion.range.start());
+
+    let mut output = String::with_capacity(input.len());
+    let mut copied_to = 0;
+    for Suggestion { range, replacement } in suggestions {
+        let (start, end) = (usize::from(range.start()), usize::from(range.end()));
+        if start < copied_to {
+            continue;
+        }
+
+        output.push_str(&input[copied_to..start]);
+        output.push_str(replacement);
+        copied_to = end;
+    }
+    output.push_str(&input[copied_to..]);
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suggestion(range: std::ops::Range<u32>, replacement: &str) -> Suggestion {
+        Suggestion {
+            range: TextRange::new(range.start.into(), range.end.into()),
+            replacement: replacement.to_string(),
+        }
+    }
+
+    #[test]
+    fn apply_in_order_of_position() {
+        let suggestions = [
+            suggestion(4..4, ")"),
+            suggestion(0..1, "["),
+            suggestion(2..2, ","),
+        ];
+
+        assert_eq!(apply_suggestions("(1 2", &suggestions), "[1, 2)");
+    }
+
+    #[test]
+    fn apply_insertions_at_the_same_offset_in_order() {
+        let suggestions = [suggestion(4..4, " then"), suggestion(4..4, " else")];
+
+        assert_eq!(apply_suggestions("if a", &suggestions), "if a then else");
+    }
+
+    #[test]
+    fn skip_overlapping_suggestions() {
+        let suggestions = [suggestion(0..3, "bar"), suggestion(2..5, "baz")];
+
+        assert_eq!(apply_suggestions("foo x", &suggestions), "bar x");
+    }
+
+    #[test]
+    fn apply_suggestions_from_parse() {
+        let input = "let a = (1 2";
+        let parse = crate::parse_repl_line(input);
+
+        let fixed = apply_suggestions(input, parse.errors().iter().flat_map(|e| e.suggestions()));
+
+        assert_eq!(fixed, "let a = (1, 2)");
+        assert!(crate::parse_repl_line(&fixed).errors().is_empty());
+    }
+}
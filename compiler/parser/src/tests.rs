@@ -1,4 +1,4 @@
-use crate::Parse;
+use crate::{Parse, ParseError};
 
 #[test]
 fn repl_line() {
@@ -7,8 +7,63 @@ fn repl_line() {
     });
 }
 
+#[test]
+fn source_file() {
+    psh_test_harness::run_test_dir("source_file", |_path, input| {
+        run_parser_test(input, crate::parse_source_file)
+    });
+}
+
 fn run_parser_test(input: &str, parsing_fn: fn(&str) -> Parse) -> String {
     let actual_parse = parsing_fn(input);
+    let mut output = actual_parse.debug_tree().to_string();
+
+    let suggestions: Vec<_> = actual_parse
+        .errors()
+        .iter()
+        .flat_map(ParseError::suggestions)
+        .collect();
+    if !suggestions.is_empty() {
+        let fixed = crate::apply_suggestions(input, suggestions);
+        let fixed_errors = parsing_fn(&fixed).errors().to_vec();
+        assert!(
+            fixed_errors.is_empty(),
+            "applying the suggestions gave {fixed:?}, which still has errors: {fixed_errors:?}"
+        );
+        output.push_str(&format!("\n--- fixed ---\n{fixed}"));
+    }
 
-    actual_parse.debug_tree().to_string()
+    output
+}
+
+#[test]
+fn incomplete_when_input_ends_early() {
+    let inputs = [
+        "1 +",
+        "1 +\n",
+        "let a =",
+        "if a then b",
+        "(1,",
+        "foo |  ",
+        "\"abc\n",
+        "\"\"\"\n  abc\n",
+        "\"${a} b",
+    ];
+    for input in inputs {
+        assert!(crate::parse_repl_line(input).is_incomplete(), "{input}");
+    }
+}
+
+#[test]
+fn not_incomplete_when_error_is_before_the_end() {
+    for input in [
+        "1",
+        "let a = 1",
+        "1 + )",
+        "let = 1",
+        "$ echo \"abc\n",
+        "\"a\\q\"",
+    ] {
+        assert!(!crate::parse_repl_line(input).is_incomplete(), "{input}");
+    }
 }
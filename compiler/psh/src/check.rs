@@ -0,0 +1,338 @@
+use std::fs;
+use std::process::ExitCode;
+
+use psh_parser::diagnostics::{LineIndex, Renderer};
+use psh_parser::ParseError;
+use serde::Serialize;
+use serde_json::json;
+use text_size::TextSize;
+
+use crate::script::PARSE_ERROR_STATUS;
+
+/// The version of the `--format json` output. It changes whenever a field is removed or
+/// changes meaning, but not when one is added.
+const JSON_FORMAT_VERSION: u32 = 1;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Format {
+    /// Errors with the source they are in, as scripts report them.
+    Human,
+    /// The format described in `docs/check-output.md`.
+    Json,
+    /// SARIF 2.1.0, for tools that annotate code with the results of static analysis.
+    Sarif,
+}
+
+impl Format {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "human" => Some(Self::Human),
+            "json" => Some(Self::Json),
+            "sarif" => Some(Self::Sarif),
+            _ => None,
+        }
+    }
+}
+
+/// Runs `psh check [--format human|json|sarif] FILE...`, which parses each file without running
+/// it and reports its syntax errors.
+pub(crate) fn run(args: impl Iterator<Item = String>) -> ExitCode {
+    let (format, paths) = match parse_args(args) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("psh: {message}");
+            eprintln!("usage: psh check [--format human|json|sarif] FILE...");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut files = Vec::with_capacity(paths.len());
+    let mut unreadable = false;
+    for path in paths {
+        match fs::read_to_string(&path) {
+            Ok(source) => files.push(CheckedFile::new(path, source)),
+            Err(err) => {
+                eprintln!("psh: cannot read {path}: {err}");
+                unreadable = true;
+            }
+        }
+    }
+
+    match format {
+        Format::Human => {
+            for file in &files {
+                let renderer = Renderer::new(&file.source)
+                    .with_path(&file.path)
+                    .with_color(crate::color_errors());
+                for error in &file.errors {
+                    eprintln!("{}", renderer.render(error));
+                }
+            }
+        }
+        Format::Json => println!("{:#}", to_json(&files)),
+        Format::Sarif => println!("{:#}", to_sarif(&files)),
+    }
+
+    if unreadable {
+        ExitCode::FAILURE
+    } else if files.iter().any(|file| !file.errors.is_empty()) {
+        ExitCode::from(PARSE_ERROR_STATUS)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<(Format, Vec<String>), String> {
+    let mut format = Format::Human;
+    let mut paths = Vec::new();
+
+    while let Some(arg) = args.next() {
+        let name = match arg.strip_prefix("--format") {
+            Some("") => args.next().ok_or("--format needs a value")?,
+            Some(rest) if rest.starts_with('=') => rest[1..].to_string(),
+            _ => {
+                paths.push(arg);
+                continue;
+            }
+        };
+        format = Format::from_name(&name).ok_or(format!("unknown format ‘{name}’"))?;
+    }
+
+    if paths.is_empty() {
+        return Err("no files to check".to_string());
+    }
+    Ok((format, paths))
+}
+
+struct CheckedFile {
+    path: String,
+    source: String,
+    line_index: LineIndex,
+    errors: Vec<ParseError>,
+}
+
+impl CheckedFile {
+    fn new(path: String, source: String) -> Self {
+        let errors = psh_parser::parse_source_file(&source).errors().to_vec();
+
+        Self {
+            path,
+            line_index: LineIndex::new(&source),
+            source,
+            errors,
+        }
+    }
+
+    /// The line and column of `offset`, both counted from one, with the column in characters.
+    fn position(&self, offset: TextSize) -> Position {
+        let line_col = self.line_index.line_col(offset);
+        let line_start = self.line_index.line_range(line_col.line).unwrap().start();
+        let before =
+            &self.source[usize::from(line_start)..usize::from(line_start) + line_col.col as usize];
+
+        Position {
+            line: line_col.line + 1,
+            column: u32::try_from(before.chars().count()).unwrap() + 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+struct Position {
+    line: u32,
+    column: u32,
+}
+
+fn to_json(files: &[CheckedFile]) -> serde_json::Value {
+    #[derive(Serialize)]
+    struct Diagnostic<'a> {
+        file: &'a str,
+        #[serde(flatten)]
+        error: &'a ParseError,
+        start: Position,
+        end: Position,
+    }
+
+    let diagnostics: Vec<_> = files
+        .iter()
+        .flat_map(|file| {
+            file.errors.iter().map(move |error| Diagnostic {
+                file: &file.path,
+                error,
+                start: file.position(error.range().start()),
+                end: file.position(error.range().end()),
+            })
+        })
+        .collect();
+
+    json!({
+        "version": JSON_FORMAT_VERSION,
+        "diagnostics": diagnostics,
+    })
+}
+
+fn to_sarif(files: &[CheckedFile]) -> serde_json::Value {
+    let mut rules: Vec<serde_json::Value> = Vec::new();
+    let mut results = Vec::new();
+
+    for file in files {
+        for error in &file.errors {
+            if !rules.iter().any(|rule| rule["id"] == error.code()) {
+                rules.push(json!({
+                    "id": error.code(),
+                    "shortDescription": {
+                        "text": format!("error while parsing {}", error.context().context_name()),
+                    },
+                }));
+            }
+
+            let range = error.range();
+            let start = file.position(range.start());
+            let end = file.position(range.end());
+            results.push(json!({
+                "ruleId": error.code(),
+                "level": "error",
+                "message": { "text": error.message() },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": file.path },
+                        "region": {
+                            "startLine": start.line,
+                            "startColumn": start.column,
+                            "endLine": end.line,
+                            "endColumn": end.column,
+                            "byteOffset": u32::from(range.start()),
+                            "byteLength": u32::from(range.len()),
+                        },
+                    },
+                }],
+            }));
+        }
+    }
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "psh",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "columnKind": "unicodeCodePoints",
+            "results": results,
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<(Format, Vec<String>), String> {
+        parse_args(args.iter().map(ToString::to_string))
+    }
+
+    fn file(source: &str) -> CheckedFile {
+        CheckedFile::new("script.psh".to_string(), source.to_string())
+    }
+
+    #[test]
+    fn parse_format_args() {
+        assert_eq!(args(&["a.psh"]), Ok((Format::Human, vec!["a.psh".into()])));
+        assert_eq!(
+            args(&["--format", "json", "a.psh", "b.psh"]),
+            Ok((Format::Json, vec!["a.psh".into(), "b.psh".into()]))
+        );
+        assert_eq!(
+            args(&["a.psh", "--format=sarif"]),
+            Ok((Format::Sarif, vec!["a.psh".into()]))
+        );
+        assert_eq!(
+            args(&["--format", "xml", "a.psh"]),
+            Err("unknown format ‘xml’".into())
+        );
+        assert_eq!(args(&["--format"]), Err("--format needs a value".into()));
+        assert_eq!(args(&[]), Err("no files to check".into()));
+    }
+
+    #[test]
+    fn json_output() {
+        let json = to_json(&[file("let a = 1\nlet s = \"é…\" )"), file("let b = 2")]);
+
+        assert_eq!(
+            json,
+            json!({
+                "version": 1,
+                "diagnostics": [{
+                    "file": "script.psh",
+                    "code": "P0053",
+                    "context": "a newline or ‘;’ between statements",
+                    "message": "found ‘)’, but expected ‘;’",
+                    "range": { "start": 26, "end": 27 },
+                    "start": { "line": 2, "column": 14 },
+                    "end": { "line": 2, "column": 15 },
+                    "found": "‘)’",
+                    "expected": ["‘;’"],
+                    "suggestions": [],
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn json_output_of_missing_token() {
+        let json = to_json(&[file("[1 2]")]);
+        let diagnostic = &json["diagnostics"][0];
+
+        assert_eq!(diagnostic["found"], serde_json::Value::Null);
+        assert_eq!(diagnostic["range"], json!({ "start": 3, "end": 3 }));
+        assert_eq!(diagnostic["start"], diagnostic["end"]);
+        assert_eq!(
+            diagnostic["suggestions"],
+            json!([{ "range": { "start": 2, "end": 2 }, "replacement": "," }])
+        );
+    }
+
+    #[test]
+    fn sarif_output() {
+        let sarif = to_sarif(&[file("[1 2]\n[3 4]")]);
+        let run = &sarif["runs"][0];
+
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(
+            run["tool"]["driver"]["rules"],
+            json!([{
+                "id": "P0007",
+                "shortDescription": {
+                    "text": "error while parsing a comma between elements of an array",
+                },
+            }])
+        );
+        assert_eq!(
+            run["results"][1],
+            json!({
+                "ruleId": "P0007",
+                "level": "error",
+                "message": { "text": "missing expected ‘,’" },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": "script.psh" },
+                        "region": {
+                            "startLine": 2,
+                            "startColumn": 4,
+                            "endLine": 2,
+                            "endColumn": 4,
+                            "byteOffset": 9,
+                            "byteLength": 0,
+                        },
+                    },
+                }],
+            })
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use std::env;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+use std::process::ExitCode;
+
+mod check;
+mod repl;
+mod script;
+mod session;
+
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1);
+
+    if let Some(script) = args.next() {
+        if script == "check" {
+            return check::run(args);
+        }
+
+        return script::run(Path::new(&script), args.collect());
+    }
+
+    match repl::run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("psh: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Whether errors written to stderr should be colored: only on a terminal, and never when
+/// `NO_COLOR` is set.
+fn color_errors() -> bool {
+    io::stderr().is_terminal() && env::var_os("NO_COLOR").is_none()
+}
@@ -0,0 +1,87 @@
+use std::env;
+use std::path::PathBuf;
+
+use psh_eval::Value;
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+
+use crate::session::{Outcome, Session};
+
+const PROMPT: &str = "psh> ";
+const CONTINUATION_PROMPT: &str = "...> ";
+const HISTORY_FILE_NAME: &str = ".psh_history";
+
+/// Reads lines until end of input, evaluating each complete submission.
+///
+/// A submission continues onto the next line while it is incomplete. Entering an empty line
+/// gives up on continuing and reports the errors instead.
+pub(crate) fn run() -> rustyline::Result<()> {
+    let mut editor = DefaultEditor::new()?;
+
+    let history_path = history_path();
+    if let Some(history_path) = &history_path {
+        // There is no history the first time psh runs.
+        let _ = editor.load_history(history_path);
+    }
+
+    let mut session = Session::with_color(crate::color_errors());
+    let mut buffer = String::new();
+
+    loop {
+        let prompt = if buffer.is_empty() {
+            PROMPT
+        } else {
+            CONTINUATION_PROMPT
+        };
+
+        let line = match editor.readline(prompt) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => {
+                buffer.clear();
+                continue;
+            }
+            Err(ReadlineError::Eof) => break,
+            Err(err) => return Err(err),
+        };
+
+        let force = !buffer.is_empty() && line.trim().is_empty();
+        buffer.push_str(&line);
+        buffer.push('\n');
+
+        if buffer.trim().is_empty() {
+            buffer.clear();
+            continue;
+        }
+
+        match session.submit(&buffer, force) {
+            Outcome::Incomplete => continue,
+            Outcome::Defined => {}
+            // Commands show their output as they would in any other shell.
+            Outcome::Value(Value::CommandOutput(output)) => {
+                print!("{}", output.stdout);
+                eprint!("{}", output.stderr);
+            }
+            Outcome::Value(value) => println!("{value}"),
+            Outcome::Errors(errors) => {
+                for error in errors {
+                    eprintln!("{error}");
+                }
+            }
+        }
+
+        editor.add_history_entry(buffer.trim_end())?;
+        buffer.clear();
+    }
+
+    if let Some(history_path) = &history_path {
+        editor.save_history(history_path)?;
+    }
+
+    Ok(())
+}
+
+fn history_path() -> Option<PathBuf> {
+    let home = env::var_os("HOME")?;
+
+    Some(PathBuf::from(home).join(HISTORY_FILE_NAME))
+}
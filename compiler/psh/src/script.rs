@@ -0,0 +1,124 @@
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+
+use psh_ast::AstNode;
+use psh_eval::{CommandOutput, Env, EvalError, Value};
+use psh_parser::diagnostics::Renderer;
+use psh_parser::ParseError;
+
+/// The exit status when the script cannot be parsed.
+pub(crate) const PARSE_ERROR_STATUS: u8 = 2;
+
+/// Runs the script at `path`, binding `args` to an array of the remaining arguments.
+pub(crate) fn run(path: &Path, args: Vec<String>) -> ExitCode {
+    let source = match fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(err) => {
+            eprintln!("psh: cannot read {}: {err}", path.display());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let path = path.display().to_string();
+    let renderer = Renderer::new(&source)
+        .with_path(&path)
+        .with_color(crate::color_errors());
+    match eval_script(&source, args) {
+        Ok(value) => ExitCode::from(exit_status(&value)),
+        Err(ScriptError::Parse(errors)) => {
+            for error in errors {
+                eprintln!("{}", renderer.render(&error));
+            }
+            ExitCode::from(PARSE_ERROR_STATUS)
+        }
+        Err(ScriptError::Eval(error)) => {
+            eprintln!(
+                "{}",
+                renderer.render_at(&error.kind.to_string(), error.range)
+            );
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[derive(Debug)]
+enum ScriptError {
+    Parse(Vec<ParseError>),
+    Eval(EvalError),
+}
+
+fn eval_script(source: &str, args: Vec<String>) -> Result<Value, ScriptError> {
+    let parse = psh_parser::parse_source_file(source);
+    if !parse.errors().is_empty() {
+        return Err(ScriptError::Parse(parse.errors().to_vec()));
+    }
+
+    let source_file = psh_ast::SourceFile::cast(parse.syntax()).unwrap();
+    let (db, stmts) = psh_hir::lower(&source_file);
+
+    let mut env = Env::default();
+    env.bind(
+        "args".into(),
+        Value::Array(args.into_iter().map(Value::String).collect()),
+    );
+
+    psh_eval::eval(&db, &stmts, &mut env).map_err(ScriptError::Eval)
+}
+
+/// The exit status for a script that evaluated to `value`.
+///
+/// A script ending in a number between 0 and 255 exits with that number, and any other number
+/// is a failure. A script ending in a command exits with the command’s status. Scripts ending
+/// in anything else succeed.
+fn exit_status(value: &Value) -> u8 {
+    match value {
+        Value::Number(n) | Value::CommandOutput(CommandOutput { status: n, .. }) => {
+            n.to_index().and_then(|n| u8::try_from(n).ok()).unwrap_or(1)
+        }
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use psh_eval::Number;
+
+    use super::*;
+
+    fn status(source: &str, args: &[&str]) -> u8 {
+        let args = args.iter().map(ToString::to_string).collect();
+        exit_status(&eval_script(source, args).unwrap())
+    }
+
+    #[test]
+    fn exit_status_from_result() {
+        assert_eq!(status("let a = 1\n3", &[]), 3);
+        assert_eq!(status("256", &[]), 1);
+        assert_eq!(status("1 / 2", &[]), 1);
+        assert_eq!(status("\"done\"", &[]), 0);
+        assert_eq!(status("let a = 3", &[]), 0);
+        assert_eq!(status("$ sh -c 'exit 4'", &[]), 4);
+    }
+
+    #[test]
+    fn args_are_bound() {
+        let value = eval_script("#!/usr/bin/env psh\nargs[1]", vec!["a".into(), "b".into()]);
+
+        assert_eq!(value.unwrap(), Value::String("b".to_string()));
+    }
+
+    #[test]
+    fn statements_separated_by_semicolons() {
+        let value = eval_script("let a = 2; let b = 3; a * b", vec![]);
+
+        assert_eq!(value.unwrap(), Value::Number(Number::from(6)));
+    }
+
+    #[test]
+    fn parse_errors_stop_the_script() {
+        let result = eval_script("let a = 1 2", vec![]);
+
+        assert!(matches!(result, Err(ScriptError::Parse(errors)) if errors.len() == 1));
+    }
+}
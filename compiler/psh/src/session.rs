@@ -0,0 +1,118 @@
+use psh_ast::AstNode;
+use psh_eval::{Env, Value};
+use psh_hir::Stmt;
+use psh_parser::diagnostics::Renderer;
+
+/// Evaluates input one submission at a time, keeping `let` bindings between submissions.
+#[derive(Debug, Default)]
+pub(crate) struct Session {
+    env: Env,
+    color: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum Outcome {
+    /// The input ended while the parser was still expecting more.
+    Incomplete,
+    /// The input only defined values, so there is nothing to show.
+    Defined,
+    Value(Value),
+    Errors(Vec<String>),
+}
+
+impl Session {
+    /// A session whose errors are colored with ANSI escapes if `color` is set.
+    pub(crate) fn with_color(color: bool) -> Self {
+        Self {
+            color,
+            ..Self::default()
+        }
+    }
+
+    /// Parses and evaluates `input`.
+    ///
+    /// Incomplete input is reported as [`Outcome::Incomplete`] so more can be added, unless
+    /// `force` is set, in which case its errors are reported instead.
+    pub(crate) fn submit(&mut self, input: &str, force: bool) -> Outcome {
+        let parse = psh_parser::parse_repl_line(input);
+        if parse.is_incomplete() && !force {
+            return Outcome::Incomplete;
+        }
+
+        let renderer = Renderer::new(input).with_color(self.color);
+        if !parse.errors().is_empty() {
+            return Outcome::Errors(parse.errors().iter().map(|e| renderer.render(e)).collect());
+        }
+
+        let source_file = psh_ast::SourceFile::cast(parse.syntax()).unwrap();
+        let (db, stmts) = psh_hir::lower(&source_file);
+
+        match psh_eval::eval(&db, &stmts, &mut self.env) {
+            Ok(_) if !matches!(stmts.last(), Some(Stmt::Expr(_))) => Outcome::Defined,
+            Ok(value) => Outcome::Value(value),
+            Err(err) => Outcome::Errors(vec![renderer.render_at(&err.kind.to_string(), err.range)]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use psh_eval::Number;
+
+    use super::*;
+
+    #[test]
+    fn bindings_persist_across_submissions() {
+        let mut session = Session::default();
+
+        assert_eq!(session.submit("let a = 2", false), Outcome::Defined);
+        assert_eq!(
+            session.submit("a * 3", false),
+            Outcome::Value(Value::Number(Number::from(6)))
+        );
+    }
+
+    #[test]
+    fn incomplete_input_waits_for_more() {
+        let mut session = Session::default();
+
+        assert_eq!(session.submit("1 +", false), Outcome::Incomplete);
+        assert_eq!(
+            session.submit("1 +\n2", false),
+            Outcome::Value(Value::Number(Number::from(3)))
+        );
+    }
+
+    #[test]
+    fn forced_incomplete_input_reports_errors() {
+        let mut session = Session::default();
+
+        assert!(matches!(session.submit("1 +", true), Outcome::Errors(_)));
+    }
+
+    #[test]
+    fn parse_errors_are_rendered_with_the_source() {
+        let mut session = Session::default();
+
+        let Outcome::Errors(errors) = session.submit("let a = 1\n[1 2]", false) else {
+            panic!("expected an error");
+        };
+        assert_eq!(
+            errors,
+            ["error[P0007]: missing expected ‘,’\n --> 2:4\n  |\n2 | [1 2]\n  |    ^ while parsing a comma between elements of an array"]
+        );
+    }
+
+    #[test]
+    fn runtime_errors_are_rendered_with_the_source() {
+        let mut session = Session::default();
+
+        let Outcome::Errors(errors) = session.submit("1 / 0", false) else {
+            panic!("expected an error");
+        };
+        assert_eq!(
+            errors,
+            ["error: cannot divide by zero\n --> 1:1\n  |\n1 | 1 / 0\n  | ^^^^^"]
+        );
+    }
+}
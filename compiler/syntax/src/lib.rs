@@ -15,18 +15,35 @@ pub enum SyntaxKind {
     IfKw,
     ThenKw,
     ElseKw,
+    TrueKw,
+    FalseKw,
+    AndKw,
+    OrKw,
+    NotKw,
     Ident,
     Integer,
     Fraction,
     String,
+    RawString,
+    MultilineString,
+    StringStart,
+    StringFragment,
+    StringEnd,
+    InterpStart,
+    InterpEnd,
     Colon,
     DoubleColon,
+    Dot,
     Comma,
+    Semicolon,
     Plus,
     Minus,
+    Arrow,
     Star,
     Slash,
     Equals,
+    DoubleEquals,
+    BangEquals,
     LParen,
     RParen,
     LBrace,
@@ -35,8 +52,16 @@ pub enum SyntaxKind {
     RBracket,
     LAngle,
     RAngle,
+    LAngleEquals,
+    RAngleEquals,
     Pipe,
+    Dollar,
+    CommandWord,
+    DoubleRAngle,
+    StderrRAngle,
+    StderrToStdout,
     Comment,
+    Shebang,
     Error,
 
     SourceFile,
@@ -45,10 +70,13 @@ pub enum SyntaxKind {
     ValueDef,
 
     StringLiteral,
+    StringInterp,
     IntLiteral,
     FractionLiteral,
+    BoolLiteral,
     UnaryExpr,
     InfixExpr,
+    PipeExpr,
 
     IfExpr,
     ThenExpr,
@@ -57,10 +85,45 @@ pub enum SyntaxKind {
 
     Unit,
     ParenExpr,
-    ParenPattern,
     TupleExpr,
+
+    UnitPattern,
+    ParenPattern,
     TuplePattern,
     TuplePatternArg,
+
+    LambdaExpr,
+    ParamList,
+    Param,
+
+    TypeSignature,
+    TypeAnnotation,
+    NamedType,
+    UnitType,
+    ParenType,
+    TupleType,
+    ArrayType,
+    MapType,
+    FunctionType,
+
+    ArrayExpr,
+    MapExpr,
+    MapEntry,
+    IndexExpr,
+    FieldExpr,
+    CallExpr,
+    ArgList,
+
+    CommandExpr,
+    Command,
+    Redirect,
+}
+
+impl SyntaxKind {
+    #[must_use]
+    pub fn is_trivia(self) -> bool {
+        matches!(self, Self::Whitespace | Self::Comment | Self::Shebang)
+    }
 }
 
 impl From<TokenKind> for SyntaxKind {
@@ -71,18 +134,35 @@ impl From<TokenKind> for SyntaxKind {
             TokenKind::IfKw => Self::IfKw,
             TokenKind::ThenKw => Self::ThenKw,
             TokenKind::ElseKw => Self::ElseKw,
+            TokenKind::TrueKw => Self::TrueKw,
+            TokenKind::FalseKw => Self::FalseKw,
+            TokenKind::AndKw => Self::AndKw,
+            TokenKind::OrKw => Self::OrKw,
+            TokenKind::NotKw => Self::NotKw,
             TokenKind::Ident => Self::Ident,
             TokenKind::Integer => Self::Integer,
             TokenKind::Fraction => Self::Fraction,
             TokenKind::String => Self::String,
+            TokenKind::RawString => Self::RawString,
+            TokenKind::MultilineString => Self::MultilineString,
+            TokenKind::StringStart => Self::StringStart,
+            TokenKind::StringFragment => Self::StringFragment,
+            TokenKind::StringEnd => Self::StringEnd,
+            TokenKind::InterpStart => Self::InterpStart,
+            TokenKind::InterpEnd => Self::InterpEnd,
             TokenKind::Colon => Self::Colon,
             TokenKind::DoubleColon => Self::DoubleColon,
+            TokenKind::Dot => Self::Dot,
             TokenKind::Comma => Self::Comma,
+            TokenKind::Semicolon => Self::Semicolon,
             TokenKind::Plus => Self::Plus,
             TokenKind::Minus => Self::Minus,
+            TokenKind::Arrow => Self::Arrow,
             TokenKind::Star => Self::Star,
             TokenKind::Slash => Self::Slash,
             TokenKind::Equals => Self::Equals,
+            TokenKind::DoubleEquals => Self::DoubleEquals,
+            TokenKind::BangEquals => Self::BangEquals,
             TokenKind::LParen => Self::LParen,
             TokenKind::RParen => Self::RParen,
             TokenKind::LBrace => Self::LBrace,
@@ -91,8 +171,16 @@ impl From<TokenKind> for SyntaxKind {
             TokenKind::RBracket => Self::RBracket,
             TokenKind::LAngle => Self::LAngle,
             TokenKind::RAngle => Self::RAngle,
+            TokenKind::LAngleEquals => Self::LAngleEquals,
+            TokenKind::RAngleEquals => Self::RAngleEquals,
             TokenKind::Pipe => Self::Pipe,
+            TokenKind::Dollar => Self::Dollar,
+            TokenKind::CommandWord => Self::CommandWord,
+            TokenKind::DoubleRAngle => Self::DoubleRAngle,
+            TokenKind::StderrRAngle => Self::StderrRAngle,
+            TokenKind::StderrToStdout => Self::StderrToStdout,
             TokenKind::Comment => Self::Comment,
+            TokenKind::Shebang => Self::Shebang,
             TokenKind::Error => Self::Error,
         }
     }